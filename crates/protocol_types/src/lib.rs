@@ -0,0 +1,92 @@
+//! Types shared by the 256M programs.
+//!
+//! Anything that crosses a program boundary (CPI arguments, or account data
+//! one program reads from another) is defined here once so every program
+//! serializes it identically.
+
+use anchor_lang::prelude::*;
+
+/// Delay applied to queued parameter changes until governance changes it.
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 48 * 60 * 60;
+
+/// Bounds on the timelock delay itself, so it can't be shortened to nothing
+/// or pushed out far enough to freeze governance.
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 60 * 60;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum AssetType {
+    USDC,
+    SOL,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum CoverageType {
+    TheftOnly,
+    TheftAndLoss,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum AssetClass {
+    Watch,
+    Jewelry,
+    Art,
+    Electronics,
+    Vehicle,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ClaimType {
+    Theft,
+    Loss,
+}
+
+/// How the deductible on a claim is worked out. The percentage-based models
+/// apply the policy's `deductible_bps` to the claimed amount.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum DeductibleModel {
+    Percentage,
+    Fixed { amount: u64 },
+    PercentageWithBounds { min: u64, max: u64 },
+    /// Percentage reduced by `reduction_bps_per_year` for every claim-free year
+    Disappearing { reduction_bps_per_year: u16 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum PaymentFrequency {
+    Monthly,
+    Annual,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum PolicyStatus {
+    Active,
+    Expired,
+    Claimed,
+    Cancelled,
+    Suspended,
+    Lapsed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ParamChangeStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+/// Price as published by an oracle account.
+///
+/// Follows the shape of a Pyth/Switchboard feed: `price * 10^expo` is the
+/// quote-currency value of one unit of the base asset, `conf` is the
+/// confidence interval in the same fixed-point units and `publish_time` is the
+/// unix timestamp of the update. Oracle accounts store this right after their
+/// 8-byte account discriminator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
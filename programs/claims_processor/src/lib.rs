@@ -1,8 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token::{Token, TokenAccount};
+use liquidity_pool::program::LiquidityPool;
+use policy_manager::{program::PolicyManager, Policy, Product};
+pub use protocol_types::{AssetType, ClaimType, DeductibleModel, ParamChangeStatus, PolicyStatus};
+use protocol_types::{DEFAULT_TIMELOCK_DELAY, MAX_TIMELOCK_DELAY, MIN_TIMELOCK_DELAY};
 
 declare_id!("C1aims111111111111111111111111111111111111111");
 
+/// Upper bound on the multisig approver set for large claims
+const MAX_APPROVERS: usize = 10;
+
 #[program]
 pub mod claims_processor {
     use super::*;
@@ -14,6 +21,7 @@ pub mod claims_processor {
     ) -> Result<()> {
         let claims_state = &mut ctx.accounts.claims_state;
         claims_state.authority = ctx.accounts.authority.key();
+        claims_state.pending_authority = None;
         claims_state.policy_manager = ctx.accounts.policy_manager.key();
         claims_state.liquidity_pool = ctx.accounts.liquidity_pool.key();
         claims_state.total_claims = 0;
@@ -24,6 +32,12 @@ pub mod claims_processor {
         claims_state.daily_auto_payout_limit = daily_auto_payout_limit;
         claims_state.daily_auto_paid = 0;
         claims_state.last_reset_day = Clock::get()?.unix_timestamp / 86400;
+        claims_state.guardian = ctx.accounts.authority.key();
+        claims_state.paused = false;
+        claims_state.claims_paused = false;
+        claims_state.payouts_paused = false;
+        claims_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        claims_state.param_change_count = 0;
         claims_state.bump = ctx.bumps.claims_state;
 
         emit!(ClaimsSystemInitializedEvent {
@@ -40,67 +54,95 @@ pub mod claims_processor {
     pub fn submit_claim(
         ctx: Context<SubmitClaim>,
         claim_type: ClaimType,
+        loss_type: LossType,
         documents_hash: [u8; 32],
         claimed_amount: u64,
+        loss_date: i64,
     ) -> Result<()> {
+        require!(!ctx.accounts.claims_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.claims_state.claims_paused, ErrorCode::ClaimsPaused);
+
         require!(claimed_amount > 0, ErrorCode::InvalidAmount);
 
         let policy = &ctx.accounts.policy;
         let claims_state = &mut ctx.accounts.claims_state;
         let claim = &mut ctx.accounts.claim;
 
+        // An expired policy still covers losses that happened before expiry
+        require!(
+            policy.status != PolicyStatus::Suspended,
+            ErrorCode::PolicySuspended
+        );
         require!(
-            policy.status == PolicyStatus::Active,
+            policy.status == PolicyStatus::Active || policy.status == PolicyStatus::Expired,
             ErrorCode::PolicyNotActive
         );
 
         require!(
-            policy.claim_count == 0,
+            policy.remaining_aggregate > 0,
             ErrorCode::PolicyAlreadyClaimed
         );
 
-        match policy.coverage_type {
-            CoverageType::TheftOnly => {
-                require!(
-                    claim_type == ClaimType::Theft,
-                    ErrorCode::ClaimTypeNotCovered
-                );
-            }
-            CoverageType::TheftAndLoss => {
-            }
-        }
+        let clock = Clock::get()?;
 
-        let deductible_amount = claimed_amount
-            .checked_mul(policy.deductible_bps as u64)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::DivisionByZero)?;
+        require!(policy.total_paid > 0, ErrorCode::PremiumNotPaid);
 
-        let payout_amount = claimed_amount
-            .checked_sub(deductible_amount)
-            .ok_or(ErrorCode::Underflow)?;
+        // No cover while a premium is overdue, whether or not the
+        // delinquency crank has suspended the policy yet
+        require!(
+            policy.status != PolicyStatus::Active || clock.unix_timestamp <= policy.next_payment_due,
+            ErrorCode::PolicySuspended
+        );
 
+        require!(loss_date <= clock.unix_timestamp, ErrorCode::InvalidLossDate);
+        require!(
+            loss_date >= policy.start_date && loss_date <= policy.expiry_date,
+            ErrorCode::LossOutsideCoverage
+        );
         require!(
-            payout_amount <= policy.insured_value,
-            ErrorCode::PayoutExceedsInsuredValue
+            loss_date >= policy.claims_waiting_ends,
+            ErrorCode::LossInWaitingPeriod
+        );
+        // next_payment_due is the paid-through date; losses after it fall in
+        // an unpaid period even if the policy has since expired
+        require!(
+            loss_date <= policy.next_payment_due,
+            ErrorCode::LossInUnpaidPeriod
         );
 
-        let clock = Clock::get()?;
+        require!(
+            ctx.accounts.product.allowed_claim_types.contains(&claim_type),
+            ErrorCode::ClaimTypeNotCovered
+        );
+
+        // Damage can't exceed the sum insured; the adjuster may revise it
+        // during manual review
+        let assessed_damage = claimed_amount.min(policy.insured_value);
+        let (deductible_amount, payout_amount) = calculate_payout(policy, assessed_damage)?;
+        require!(payout_amount > 0, ErrorCode::ClaimBelowDeductible);
+
         let claim_id = claims_state.total_claims + 1;
 
         claim.claim_id = claim_id;
         claim.policy = ctx.accounts.policy.key();
         claim.customer = policy.customer;
         claim.claim_type = claim_type;
+        claim.loss_type = loss_type;
+        claim.claimed_amount = claimed_amount;
+        claim.assessed_damage = assessed_damage;
         claim.claim_amount = payout_amount;
+        claim.approved_amount = 0;
         claim.documents_hash = documents_hash;
+        claim.loss_date = loss_date;
         claim.submitted_at = clock.unix_timestamp;
         claim.status = ClaimStatus::Submitted;
         claim.ai_decision = AIDecision::Pending;
         claim.ai_confidence = 0;
+        claim.auto_approved = false;
         claim.manual_reviewer = None;
         claim.reviewed_at = None;
-        claim.payout_tx = None;
+        claim.review_reason_hash = [0; 32];
+        claim.paid_at = None;
         claim.bump = ctx.bumps.claim;
 
         claims_state.total_claims = claim_id;
@@ -110,7 +152,11 @@ pub mod claims_processor {
             policy_id: policy.policy_id,
             customer: policy.customer,
             claim_type,
+            loss_type,
+            claimed_amount,
             claim_amount: payout_amount,
+            deductible_amount,
+            loss_date,
             timestamp: clock.unix_timestamp,
         });
 
@@ -123,8 +169,16 @@ pub mod claims_processor {
         decision: AIDecision,
         confidence: u8,
     ) -> Result<()> {
+        require!(!ctx.accounts.claims_state.paused, ErrorCode::ProgramPaused);
+
         require!(confidence <= 100, ErrorCode::InvalidConfidence);
+        // Pending is the state before review, not a verdict
+        require!(decision != AIDecision::Pending, ErrorCode::InvalidAIDecision);
 
+        let oracle_config = &mut ctx.accounts.oracle_config;
+        require!(oracle_config.enabled, ErrorCode::OracleDisabled);
+
+        let claims_state = &mut ctx.accounts.claims_state;
         let claim = &mut ctx.accounts.claim;
 
         require!(
@@ -135,35 +189,76 @@ pub mod claims_processor {
         claim.ai_decision = decision;
         claim.ai_confidence = confidence;
 
+        let now = Clock::get()?.unix_timestamp;
+
         match decision {
-            AIDecision::Approved => {
-                if confidence >= 80 {
+            AIDecision::Approved if confidence >= oracle_config.confidence_threshold => {
+                let current_day = now / 86400;
+                if current_day > claims_state.last_reset_day {
+                    claims_state.daily_auto_paid = 0;
+                    claims_state.last_reset_day = current_day;
+                }
+
+                let daily_total = claims_state.daily_auto_paid
+                    .checked_add(claim.claim_amount)
+                    .ok_or(ErrorCode::Overflow)?;
+
+                // Small, high-confidence claims skip manual review. The
+                // daily budget is reserved here so approvals can never
+                // outrun it before the payouts are cranked.
+                if claim.claim_amount <= claims_state.max_auto_payout
+                    && daily_total <= claims_state.daily_auto_payout_limit
+                {
+                    claim.status = ClaimStatus::Approved;
+                    claim.auto_approved = true;
+                    claim.approved_amount = claim.claim_amount;
+                    claims_state.daily_auto_paid = daily_total;
+
+                    emit!(ClaimAutoApprovedEvent {
+                        claim_id: claim.claim_id,
+                        amount: claim.claim_amount,
+                        confidence,
+                        daily_auto_paid: claims_state.daily_auto_paid,
+                        timestamp: now,
+                    });
+                } else {
                     claim.status = ClaimStatus::UnderReview;
                 }
             }
-            AIDecision::Rejected => {
-                claim.status = ClaimStatus::UnderReview;
-            }
-            AIDecision::ManualReview => {
+            // Everything short of a confident approval goes to a human, and
+            // leaving Submitted stops other oracles from reviewing it again
+            AIDecision::Approved | AIDecision::Rejected | AIDecision::ManualReview => {
                 claim.status = ClaimStatus::UnderReview;
             }
-            _ => {}
+            AIDecision::Pending => return err!(ErrorCode::InvalidAIDecision),
         }
 
+        oracle_config.reviews_submitted = oracle_config.reviews_submitted
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
         emit!(AIReviewCompletedEvent {
             claim_id: claim.claim_id,
+            oracle: ctx.accounts.ai_oracle.key(),
             decision,
             confidence,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
         });
 
         msg!("AI reviewed claim {}: {:?} ({}% confidence)", claim.claim_id, decision, confidence);
         Ok(())
     }
 
+    /// `assessed_damage` replaces the damage figure from submission when the
+    /// adjuster's assessment differs; the payout is recomputed from it.
+    /// `approved_amount` lets the reviewer approve less than that payout, with
+    /// the justification referenced by `reason_hash`.
     pub fn manual_review_claim(
         ctx: Context<ManualReviewClaim>,
         approve: bool,
+        assessed_damage: Option<u64>,
+        approved_amount: Option<u64>,
+        reason_hash: [u8; 32],
     ) -> Result<()> {
         require!(
             ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
@@ -179,8 +274,26 @@ pub mod claims_processor {
 
         claim.manual_reviewer = Some(ctx.accounts.authority.key());
         claim.reviewed_at = Some(Clock::get()?.unix_timestamp);
+        claim.review_reason_hash = reason_hash;
 
         if approve {
+            if let Some(damage) = assessed_damage {
+                let policy = &ctx.accounts.policy;
+                require!(damage <= policy.insured_value, ErrorCode::PayoutExceedsInsuredValue);
+                require!(damage <= claim.claimed_amount, ErrorCode::DamageExceedsClaimedAmount);
+
+                let (_, payout_amount) = calculate_payout(policy, damage)?;
+                require!(payout_amount > 0, ErrorCode::ClaimBelowDeductible);
+
+                claim.assessed_damage = damage;
+                claim.claim_amount = payout_amount;
+            }
+
+            let amount = approved_amount.unwrap_or(claim.claim_amount);
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            require!(amount <= claim.claim_amount, ErrorCode::ApprovedAmountTooHigh);
+
+            claim.approved_amount = amount;
             claim.status = ClaimStatus::Approved;
         } else {
             claim.status = ClaimStatus::Rejected;
@@ -195,21 +308,26 @@ pub mod claims_processor {
             claim_id: claim.claim_id,
             reviewer: ctx.accounts.authority.key(),
             approved: approve,
+            claimed_amount: claim.claimed_amount,
+            assessed_damage: claim.assessed_damage,
+            approved_amount: claim.approved_amount,
+            reason_hash,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Manual review claim {}: {}", claim.claim_id, if approve { "APPROVED" } else { "REJECTED" });
+        msg!("Manual review claim {}: {} (approved amount: {})", claim.claim_id, if approve { "APPROVED" } else { "REJECTED" }, claim.approved_amount);
         Ok(())
     }
 
-    pub fn execute_claim_payout(
-        ctx: Context<ExecuteClaimPayout>,
+    /// ClaimApproval accounts for the claim are passed as remaining accounts,
+    /// each followed by the approver it belongs to. They are closed here and
+    /// their rent goes back to the approvers.
+    pub fn execute_claim_payout<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteClaimPayout<'info>>,
         asset_type: AssetType,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
-            ErrorCode::Unauthorized
-        );
+        require!(!ctx.accounts.claims_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.claims_state.payouts_paused, ErrorCode::PayoutsPaused);
 
         let claim = &mut ctx.accounts.claim;
         let claims_state = &mut ctx.accounts.claims_state;
@@ -220,47 +338,106 @@ pub mod claims_processor {
         );
 
         require!(
-            claim.payout_tx.is_none(),
+            claim.paid_at.is_none(),
             ErrorCode::ClaimAlreadyPaid
         );
 
-        let current_day = Clock::get()?.unix_timestamp / 86400;
-        if current_day > claims_state.last_reset_day {
-            claims_state.daily_auto_paid = 0;
-            claims_state.last_reset_day = current_day;
+        // Other claims on the policy may have been paid since this one was
+        // approved, so cap at what is left of the sum insured
+        let payout_amount = claim.approved_amount.min(ctx.accounts.policy.remaining_aggregate);
+        require!(payout_amount > 0, ErrorCode::PolicyAlreadyClaimed);
+
+        // Auto-approved claims can be paid by any cranker while they still fit
+        // under the auto-payout cap. Everything else, including auto-approved
+        // claims caught by a since-lowered cap, needs the authority (and the
+        // multisig below when over the cap).
+        let permissionless = claim.auto_approved
+            && claim.approved_amount <= claims_state.max_auto_payout;
+        if !permissionless {
+            require!(
+                ctx.accounts.authority.key() == claims_state.authority,
+                ErrorCode::Unauthorized
+            );
         }
 
-        if claim.claim_amount > claims_state.max_auto_payout {
-            msg!("⚠ Large claim requiring multisig approval");
+        // Large claims also need M-of-N approver sign-off. Approvals are
+        // re-checked against the current approver set, so removed approvers
+        // no longer count.
+        let approvals = close_approvals(
+            ctx.remaining_accounts,
+            claim.key(),
+            ctx.accounts.approver_set.as_deref(),
+        )?;
+        if claim.approved_amount > claims_state.max_auto_payout {
+            let approver_set = ctx.accounts.approver_set
+                .as_ref()
+                .ok_or(ErrorCode::ApproverSetNotConfigured)?;
+            require!(
+                approvals >= approver_set.threshold as usize,
+                ErrorCode::InsufficientApprovals
+            );
         }
 
-        if claims_state.daily_auto_paid + claim.claim_amount > claims_state.daily_auto_payout_limit {
-            msg!("⚠ Daily limit reached, requiring manual approval");
-        }
+        // Funds leave the pool through liquidity_pool::execute_payout, signed by
+        // our claims_authority PDA. Any failure there (e.g. insufficient pool
+        // liquidity) aborts the whole instruction.
+        let authority_seeds: [&[u8]; 2] = [b"claims_authority", &[ctx.bumps.claims_authority]];
+        let signer = &[&authority_seeds[..]];
+
+        let payout_ctx = CpiContext::new_with_signer(
+            ctx.accounts.liquidity_pool_program.to_account_info(),
+            liquidity_pool::cpi::accounts::ExecutePayout {
+                pool_state: ctx.accounts.pool_state.to_account_info(),
+                pool_vault_usdc: ctx.accounts.pool_vault_usdc.to_account_info(),
+                pool_vault_sol: ctx.accounts.pool_vault_sol.to_account_info(),
+                claimant_usdc: ctx.accounts.claimant_token_account.to_account_info(),
+                claimant: ctx.accounts.claimant.to_account_info(),
+                sol_price_feed: ctx.accounts.sol_price_feed.to_account_info(),
+                claims_processor: ctx.accounts.claims_authority.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer,
+        );
+        liquidity_pool::cpi::execute_payout(payout_ctx, payout_amount, asset_type)?;
+
+        let mark_claimed_ctx = CpiContext::new_with_signer(
+            ctx.accounts.policy_manager_program.to_account_info(),
+            policy_manager::cpi::accounts::MarkPolicyClaimed {
+                protocol_state: ctx.accounts.protocol_state.to_account_info(),
+                policy: ctx.accounts.policy.to_account_info(),
+                customer: ctx.accounts.customer.to_account_info(),
+                claims_processor: ctx.accounts.claims_authority.to_account_info(),
+            },
+            signer,
+        );
+        policy_manager::cpi::mark_policy_claimed(
+            mark_claimed_ctx,
+            payout_amount,
+            claim.loss_type == LossType::Total,
+        )?;
 
+        claim.approved_amount = payout_amount;
         claim.status = ClaimStatus::Paid;
-        claim.payout_tx = Some([0u8; 64]);
+        claim.paid_at = Some(Clock::get()?.unix_timestamp);
 
         claims_state.approved_claims = claims_state.approved_claims
             .checked_add(1)
             .ok_or(ErrorCode::Overflow)?;
         claims_state.total_paid_out = claims_state.total_paid_out
-            .checked_add(claim.claim_amount)
-            .ok_or(ErrorCode::Overflow)?;
-        claims_state.daily_auto_paid = claims_state.daily_auto_paid
-            .checked_add(claim.claim_amount)
+            .checked_add(claim.approved_amount)
             .ok_or(ErrorCode::Overflow)?;
 
         emit!(ClaimPaidEvent {
             claim_id: claim.claim_id,
             policy_id: ctx.accounts.policy.policy_id,
             customer: claim.customer,
-            amount: claim.claim_amount,
+            amount: claim.approved_amount,
             asset_type,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Claim {} paid: {} {:?}", claim.claim_id, claim.claim_amount, asset_type);
+        msg!("Claim {} paid: {} {:?}", claim.claim_id, claim.approved_amount, asset_type);
         Ok(())
     }
 
@@ -301,7 +478,7 @@ pub mod claims_processor {
     }
 
     pub fn update_payout_limits(
-        ctx: Context<UpdatePayoutLimits>,
+        ctx: Context<QueueParamChange>,
         max_auto_payout: u64,
         daily_auto_payout_limit: u64,
     ) -> Result<()> {
@@ -310,21 +487,502 @@ pub mod claims_processor {
             ErrorCode::Unauthorized
         );
 
-        let claims_state = &mut ctx.accounts.claims_state;
-        claims_state.max_auto_payout = max_auto_payout;
-        claims_state.daily_auto_payout_limit = daily_auto_payout_limit;
-
-        emit!(PayoutLimitsUpdatedEvent {
+        let change = ParamChange::PayoutLimits {
             max_auto_payout,
             daily_auto_payout_limit,
+        };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.claims_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn register_ai_oracle(
+        ctx: Context<RegisterAIOracle>,
+        oracle: Pubkey,
+        confidence_threshold: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(confidence_threshold <= 100, ErrorCode::InvalidConfidence);
+
+        let oracle_config = &mut ctx.accounts.oracle_config;
+        oracle_config.oracle = oracle;
+        oracle_config.enabled = true;
+        oracle_config.confidence_threshold = confidence_threshold;
+        oracle_config.reviews_submitted = 0;
+        oracle_config.registered_at = Clock::get()?.unix_timestamp;
+        oracle_config.bump = ctx.bumps.oracle_config;
+
+        emit!(AIOracleRegisteredEvent {
+            oracle,
+            confidence_threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("AI oracle registered: {} (threshold {}%)", oracle, confidence_threshold);
+        Ok(())
+    }
+
+    pub fn update_ai_oracle(
+        ctx: Context<UpdateAIOracle>,
+        enabled: bool,
+        confidence_threshold: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(confidence_threshold <= 100, ErrorCode::InvalidConfidence);
+
+        let oracle_config = &mut ctx.accounts.oracle_config;
+        oracle_config.enabled = enabled;
+        oracle_config.confidence_threshold = confidence_threshold;
+
+        emit!(AIOracleUpdatedEvent {
+            oracle: oracle_config.oracle,
+            enabled,
+            confidence_threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("AI oracle {} updated: enabled={}, threshold {}%", oracle_config.oracle, enabled, confidence_threshold);
+        Ok(())
+    }
+
+    pub fn set_approvers(
+        ctx: Context<SetApprovers>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            !approvers.is_empty() && approvers.len() <= MAX_APPROVERS,
+            ErrorCode::InvalidApproverSet
+        );
+        require!(
+            threshold > 0 && threshold as usize <= approvers.len(),
+            ErrorCode::InvalidApproverSet
+        );
+        for (i, approver) in approvers.iter().enumerate() {
+            require!(
+                !approvers[..i].contains(approver),
+                ErrorCode::InvalidApproverSet
+            );
+        }
+
+        let approver_set = &mut ctx.accounts.approver_set;
+        approver_set.approvers = approvers.clone();
+        approver_set.threshold = threshold;
+        approver_set.bump = ctx.bumps.approver_set;
+
+        emit!(ApproverSetUpdatedEvent {
+            approvers,
+            threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Approver set updated: {}-of-{}", threshold, approver_set.approvers.len());
+        Ok(())
+    }
+
+    pub fn approve_claim_payout(ctx: Context<ApproveClaimPayout>) -> Result<()> {
+        require!(!ctx.accounts.claims_state.paused, ErrorCode::ProgramPaused);
+
+        let approver = ctx.accounts.approver.key();
+        require!(
+            ctx.accounts.approver_set.approvers.contains(&approver),
+            ErrorCode::NotAnApprover
+        );
+
+        let claim = &ctx.accounts.claim;
+
+        require!(
+            claim.status == ClaimStatus::Approved,
+            ErrorCode::ClaimNotApproved
+        );
+
+        require!(
+            claim.paid_at.is_none(),
+            ErrorCode::ClaimAlreadyPaid
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+
+        let approval = &mut ctx.accounts.claim_approval;
+        approval.claim = claim.key();
+        approval.approver = approver;
+        approval.approved_at = now;
+        approval.bump = ctx.bumps.claim_approval;
+
+        emit!(ClaimPayoutApprovedEvent {
+            claim_id: claim.claim_id,
+            approver,
+            threshold: ctx.accounts.approver_set.threshold,
+            timestamp: now,
+        });
+
+        msg!("Claim {} payout approved by {}", claim.claim_id, approver);
+        Ok(())
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // The new key only takes over once it signs accept_authority, so a
+        // mistyped address can be cancelled instead of locking out the admin.
+        let claims_state = &mut ctx.accounts.claims_state;
+        claims_state.pending_authority = Some(new_authority);
+
+        emit!(AuthorityTransferProposedEvent {
+            current_authority: claims_state.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transfer proposed: {} -> {}", claims_state.authority, new_authority);
+        Ok(())
+    }
+
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        let claims_state = &mut ctx.accounts.claims_state;
+        let new_authority = claims_state.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
+        require!(
+            ctx.accounts.new_authority.key() == new_authority,
+            ErrorCode::Unauthorized
+        );
+
+        let old_authority = claims_state.authority;
+        claims_state.authority = new_authority;
+        claims_state.pending_authority = None;
+
+        emit!(AuthorityUpdatedEvent {
+            old_authority,
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority updated: {} -> {}", old_authority, new_authority);
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(
+        ctx: Context<CancelAuthorityTransfer>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let claims_state = &mut ctx.accounts.claims_state;
+        let cancelled_authority = claims_state.pending_authority.take().ok_or(ErrorCode::NoPendingAuthority)?;
+
+        emit!(AuthorityTransferCancelledEvent {
+            authority: claims_state.authority,
+            cancelled_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transfer to {} cancelled", cancelled_authority);
+        Ok(())
+    }
+
+    pub fn update_timelock_delay(
+        ctx: Context<QueueParamChange>,
+        new_delay: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TimelockDelay { new_delay };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.claims_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    /// Applies a queued change once its ETA has passed. Permissionless, since
+    /// the change itself was already authorized when it was queued.
+    pub fn execute_parameter_change(
+        ctx: Context<ExecuteParameterChange>,
+    ) -> Result<()> {
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= pending_change.eta, ErrorCode::TimelockNotElapsed);
+
+        validate_param_change(&pending_change.change)?;
+        apply_param_change(&mut ctx.accounts.claims_state, pending_change.change)?;
+        pending_change.status = ParamChangeStatus::Executed;
+
+        emit!(ParamChangeExecutedEvent {
+            id: pending_change.id,
+            change: pending_change.change,
+            timestamp: now,
+        });
+
+        msg!("Parameter change {} executed", pending_change.id);
+        Ok(())
+    }
+
+    pub fn cancel_parameter_change(
+        ctx: Context<CancelParameterChange>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        pending_change.status = ParamChangeStatus::Cancelled;
+
+        emit!(ParamChangeCancelledEvent {
+            id: pending_change.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Parameter change {} cancelled", pending_change.id);
+        Ok(())
+    }
+
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.claims_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let claims_state = &mut ctx.accounts.claims_state;
+        let old_guardian = claims_state.guardian;
+        claims_state.guardian = new_guardian;
+
+        emit!(GuardianUpdatedEvent {
+            old_guardian,
+            new_guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Guardian updated: {} -> {}", old_guardian, new_guardian);
+        Ok(())
+    }
+
+    /// Circuit breaker. Only the guardian can flip these, so a pause doesn't
+    /// have to wait on the authority key or the timelock.
+    pub fn set_pause_state(
+        ctx: Context<SetPauseState>,
+        paused: bool,
+        claims_paused: bool,
+        payouts_paused: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.guardian.key() == ctx.accounts.claims_state.guardian,
+            ErrorCode::Unauthorized
+        );
+
+        let claims_state = &mut ctx.accounts.claims_state;
+        claims_state.paused = paused;
+        claims_state.claims_paused = claims_paused;
+        claims_state.payouts_paused = payouts_paused;
+
+        emit!(PauseStateUpdatedEvent {
+            guardian: ctx.accounts.guardian.key(),
+            paused,
+            claims_paused,
+            payouts_paused,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Payout limits updated: max={}, daily={}", max_auto_payout, daily_auto_payout_limit);
+        msg!("Pause state updated: paused={}, claims_paused={}, payouts_paused={}", paused, claims_paused, payouts_paused);
         Ok(())
     }
 }
 
+/// Stores `change` as the next pending change, executable after the current timelock delay
+fn queue_param_change(
+    claims_state: &mut ClaimsState,
+    pending_change: &mut PendingChange,
+    change: ParamChange,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    claims_state.param_change_count = claims_state.param_change_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    pending_change.id = claims_state.param_change_count;
+    pending_change.change = change;
+    pending_change.queued_at = now;
+    pending_change.eta = now
+        .checked_add(claims_state.timelock_delay)
+        .ok_or(ErrorCode::Overflow)?;
+    pending_change.status = ParamChangeStatus::Queued;
+    pending_change.bump = bump;
+
+    emit!(ParamChangeQueuedEvent {
+        id: pending_change.id,
+        change,
+        eta: pending_change.eta,
+        timestamp: now,
+    });
+
+    msg!("Parameter change {} queued: {:?}, executable at {}", pending_change.id, change, pending_change.eta);
+    Ok(())
+}
+
+/// Bounds checks shared by queueing and execution
+fn validate_param_change(change: &ParamChange) -> Result<()> {
+    if let ParamChange::TimelockDelay { new_delay } = change {
+        require!(
+            (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(new_delay),
+            ErrorCode::InvalidTimelockDelay
+        );
+    }
+    Ok(())
+}
+
+fn apply_param_change(claims_state: &mut ClaimsState, change: ParamChange) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    match change {
+        ParamChange::PayoutLimits { max_auto_payout, daily_auto_payout_limit } => {
+            claims_state.max_auto_payout = max_auto_payout;
+            claims_state.daily_auto_payout_limit = daily_auto_payout_limit;
+
+            emit!(PayoutLimitsUpdatedEvent {
+                max_auto_payout,
+                daily_auto_payout_limit,
+                timestamp,
+            });
+
+            msg!("Payout limits updated: max={}, daily={}", max_auto_payout, daily_auto_payout_limit);
+        }
+        ParamChange::TimelockDelay { new_delay } => {
+            let old_delay = claims_state.timelock_delay;
+            claims_state.timelock_delay = new_delay;
+
+            emit!(TimelockDelayUpdatedEvent {
+                old_delay,
+                new_delay,
+                timestamp,
+            });
+
+            msg!("Timelock delay updated: {}s -> {}s", old_delay, new_delay);
+        }
+    }
+
+    Ok(())
+}
+
+/// Counts distinct, current approvers among the ClaimApproval accounts for
+/// `claim`, supplied as `(approval, approver)` pairs, and closes each approval
+/// back to the approver who paid its rent.
+fn close_approvals<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    claim: Pubkey,
+    approver_set: Option<&ApproverSet>,
+) -> Result<usize> {
+    let pairs = accounts.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), ErrorCode::InvalidApproval);
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(pairs.len());
+
+    for pair in pairs {
+        let (approval_info, approver_info) = (&pair[0], &pair[1]);
+        // A closed approval is handed back to the system program, so passing
+        // the same one twice fails here
+        require!(approval_info.owner == &crate::ID, ErrorCode::InvalidApproval);
+
+        let approval = Account::<ClaimApproval>::try_from(approval_info)?;
+        require!(approval.claim == claim, ErrorCode::InvalidApproval);
+        require!(approver_info.key() == approval.approver, ErrorCode::InvalidApproval);
+
+        let current = approver_set.is_some_and(|set| set.approvers.contains(&approval.approver));
+        if current && !seen.contains(&approval.approver) {
+            seen.push(approval.approver);
+        }
+
+        approval.close(approver_info.clone())?;
+    }
+
+    Ok(seen.len())
+}
+
+/// Deductible and payout for `damage` on `policy`, with the payout capped by
+/// the per-claim limit and what is left of the sum insured.
+fn calculate_payout(policy: &Policy, damage: u64) -> Result<(u64, u64)> {
+    let (deductible, payout) = calculate_deductible(
+        policy.deductible_model,
+        damage,
+        policy.deductible_bps,
+        policy.claim_free_years,
+    )?;
+
+    Ok((deductible, payout.min(policy.per_claim_limit).min(policy.remaining_aggregate)))
+}
+
+/// Splits `claimed_amount` into `(deductible, payout)` under `model`. The
+/// deductible never exceeds the claimed amount.
+pub fn calculate_deductible(
+    model: DeductibleModel,
+    claimed_amount: u64,
+    deductible_bps: u16,
+    claim_free_years: u8,
+) -> Result<(u64, u64)> {
+    let percentage_of = |bps: u64| -> Result<u64> {
+        let amount = (claimed_amount as u128) * (bps as u128) / 10000;
+        u64::try_from(amount).map_err(|_| error!(ErrorCode::Overflow))
+    };
+
+    let deductible = match model {
+        DeductibleModel::Percentage => percentage_of(deductible_bps as u64)?,
+        DeductibleModel::Fixed { amount } => amount,
+        DeductibleModel::PercentageWithBounds { min, max } => {
+            percentage_of(deductible_bps as u64)?.clamp(min, max)
+        }
+        DeductibleModel::Disappearing { reduction_bps_per_year } => {
+            let reduction = reduction_bps_per_year as u64 * claim_free_years as u64;
+            percentage_of((deductible_bps as u64).saturating_sub(reduction))?
+        }
+    }
+    .min(claimed_amount);
+
+    Ok((deductible, claimed_amount - deductible))
+}
+
 #[derive(Accounts)]
 pub struct InitializeClaimsSystem<'info> {
     #[account(
@@ -357,10 +1015,26 @@ pub struct SubmitClaim<'info> {
     )]
     pub claims_state: Account<'info, ClaimsState>,
 
-    /// CHECK: Policy account from policy-manager program
-    #[account(mut)]
+    /// Policy account owned by the policy-manager program
+    #[account(
+        seeds = [
+            b"policy",
+            policy.customer.as_ref(),
+            &policy.policy_id.to_le_bytes()
+        ],
+        seeds::program = policy_manager::ID,
+        bump = policy.bump
+    )]
     pub policy: Account<'info, Policy>,
 
+    /// Product the policy was written under
+    #[account(
+        seeds = [b"product".as_ref(), &policy.product_id.to_le_bytes()],
+        seeds::program = policy_manager::ID,
+        bump = product.bump
+    )]
+    pub product: Account<'info, Product>,
+
     #[account(
         init,
         payer = customer,
@@ -384,22 +1058,37 @@ pub struct SubmitClaim<'info> {
 pub struct AIReviewClaim<'info> {
     #[account(
         mut,
-        seeds = [
-            b"claim",
-            claim.policy.as_ref(),
-            &claim.claim_id.to_le_bytes()
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"claim",
+            claim.policy.as_ref(),
+            &claim.claim_id.to_le_bytes()
         ],
         bump = claim.bump
     )]
     pub claim: Account<'info, Claim>,
 
-    /// CHECK: AI oracle signer
+    /// Registration of the signing oracle; unregistered keys have no such account
+    #[account(
+        mut,
+        seeds = [b"ai_oracle", ai_oracle.key().as_ref()],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, AIOracle>,
+
     pub ai_oracle: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct ManualReviewClaim<'info> {
     #[account(
+        mut,
         seeds = [b"claims_state"],
         bump = claims_state.bump
     )]
@@ -416,6 +1105,10 @@ pub struct ManualReviewClaim<'info> {
     )]
     pub claim: Account<'info, Claim>,
 
+    /// Policy account owned by the policy-manager program
+    #[account(address = claim.policy)]
+    pub policy: Account<'info, Policy>,
+
     pub authority: Signer<'info>,
 }
 
@@ -439,10 +1132,22 @@ pub struct ExecuteClaimPayout<'info> {
     )]
     pub claim: Account<'info, Claim>,
 
-    /// CHECK: Policy from policy-manager
+    /// Policy account owned by the policy-manager program
+    #[account(
+        mut,
+        address = claim.policy
+    )]
     pub policy: Account<'info, Policy>,
 
-    /// CHECK: Pool state from liquidity-pool
+    /// CHECK: Policy manager protocol state, validated by the policy manager program
+    #[account(mut)]
+    pub protocol_state: AccountInfo<'info>,
+
+    /// CHECK: Customer record from policy-manager, validated by the policy manager program
+    #[account(mut)]
+    pub customer: AccountInfo<'info>,
+
+    /// CHECK: Pool state from liquidity-pool, validated by the liquidity pool program
     #[account(mut)]
     pub pool_state: AccountInfo<'info>,
 
@@ -450,26 +1155,55 @@ pub struct ExecuteClaimPayout<'info> {
     #[account(mut)]
     pub pool_vault_usdc: Account<'info, TokenAccount>,
 
-    /// CHECK: Pool SOL vault
+    /// CHECK: Pool SOL vault, validated by the liquidity pool program
     #[account(mut)]
     pub pool_vault_sol: AccountInfo<'info>,
 
-    #[account(mut)]
+    /// CHECK: SOL/USD oracle price account, validated by the liquidity pool program
+    pub sol_price_feed: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == claim.customer @ ErrorCode::InvalidClaimant
+    )]
     pub claimant_token_account: Account<'info, TokenAccount>,
 
     /// CHECK: Claimant
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = claimant.key() == claim.customer @ ErrorCode::InvalidClaimant
+    )]
     pub claimant: AccountInfo<'info>,
 
-    /// CHECK: Liquidity pool program
-    pub liquidity_pool_program: AccountInfo<'info>,
+    /// CHECK: PDA that signs cross-program calls on behalf of the claims processor
+    #[account(
+        seeds = [b"claims_authority"],
+        bump
+    )]
+    pub claims_authority: AccountInfo<'info>,
 
-    /// CHECK: Policy manager program
-    pub policy_manager_program: AccountInfo<'info>,
+    #[account(
+        constraint = liquidity_pool_program.key() == claims_state.liquidity_pool @ ErrorCode::InvalidProgram
+    )]
+    pub liquidity_pool_program: Program<'info, LiquidityPool>,
+
+    #[account(
+        constraint = policy_manager_program.key() == claims_state.policy_manager @ ErrorCode::InvalidProgram
+    )]
+    pub policy_manager_program: Program<'info, PolicyManager>,
 
+    /// Only required for claims above `max_auto_payout`
+    #[account(
+        seeds = [b"approver_set"],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Option<Account<'info, ApproverSet>>,
+
+    /// Claims authority, or any cranker for auto-approved claims
     pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -496,7 +1230,209 @@ pub struct RejectClaim<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdatePayoutLimits<'info> {
+#[instruction(oracle: Pubkey)]
+pub struct RegisterAIOracle<'info> {
+    #[account(
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AIOracle::INIT_SPACE,
+        seeds = [b"ai_oracle", oracle.as_ref()],
+        bump
+    )]
+    pub oracle_config: Account<'info, AIOracle>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAIOracle<'info> {
+    #[account(
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        mut,
+        seeds = [b"ai_oracle", oracle_config.oracle.as_ref()],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, AIOracle>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetApprovers<'info> {
+    #[account(
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ApproverSet::INIT_SPACE,
+        seeds = [b"approver_set"],
+        bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveClaimPayout<'info> {
+    #[account(
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        seeds = [b"approver_set"],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        seeds = [
+            b"claim",
+            claim.policy.as_ref(),
+            &claim.claim_id.to_le_bytes()
+        ],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    /// One approval per approver per claim; a second signature fails on init
+    #[account(
+        init,
+        payer = approver,
+        space = 8 + ClaimApproval::INIT_SPACE,
+        seeds = [b"claim_approval", claim.key().as_ref(), approver.key().as_ref()],
+        bump
+    )]
+    pub claim_approval: Account<'info, ClaimApproval>,
+
+    #[account(mut)]
+    pub approver: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueParamChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingChange::INIT_SPACE,
+        seeds = [b"param_change".as_ref(), &(claims_state.param_change_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelParameterChange<'info> {
+    #[account(
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
     #[account(
         mut,
         seeds = [b"claims_state"],
@@ -507,10 +1443,23 @@ pub struct UpdatePayoutLimits<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SetPauseState<'info> {
+    #[account(
+        mut,
+        seeds = [b"claims_state"],
+        bump = claims_state.bump
+    )]
+    pub claims_state: Account<'info, ClaimsState>,
+
+    pub guardian: Signer<'info>,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct ClaimsState {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
     pub policy_manager: Pubkey,
     pub liquidity_pool: Pubkey,
     pub total_claims: u64,
@@ -521,6 +1470,23 @@ pub struct ClaimsState {
     pub daily_auto_payout_limit: u64,
     pub daily_auto_paid: u64,
     pub last_reset_day: i64,
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub claims_paused: bool,
+    pub payouts_paused: bool,
+    pub timelock_delay: i64,
+    pub param_change_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingChange {
+    pub id: u64,
+    pub change: ParamChange,
+    pub queued_at: i64,
+    pub eta: i64,
+    pub status: ParamChangeStatus,
     pub bump: u8,
 }
 
@@ -531,42 +1497,58 @@ pub struct Claim {
     pub policy: Pubkey,
     pub customer: Pubkey,
     pub claim_type: ClaimType,
+    pub loss_type: LossType,
+    pub claimed_amount: u64,
+    pub assessed_damage: u64,
     pub claim_amount: u64,
+    pub approved_amount: u64,
     pub documents_hash: [u8; 32],
+    pub loss_date: i64,
     pub submitted_at: i64,
     pub status: ClaimStatus,
     pub ai_decision: AIDecision,
     pub ai_confidence: u8,
+    pub auto_approved: bool,
     pub manual_reviewer: Option<Pubkey>,
     pub reviewed_at: Option<i64>,
-    pub payout_tx: Option<[u8; 64]>,
+    pub review_reason_hash: [u8; 32],
+    pub paid_at: Option<i64>,
     pub bump: u8,
 }
 
 #[account]
 #[derive(InitSpace)]
-pub struct Policy {
-    pub policy_id: u64,
-    pub customer: Pubkey,
-    pub coverage_type: CoverageType,
-    pub watch_details_hash: [u8; 32],
-    pub insured_value: u64,
-    pub premium: u64,
-    pub deductible_bps: u16,
-    pub payment_frequency: PaymentFrequency,
-    pub start_date: i64,
-    pub expiry_date: i64,
-    pub status: PolicyStatus,
-    pub total_paid: u64,
-    pub next_payment_due: i64,
-    pub claim_count: u8,
+pub struct AIOracle {
+    pub oracle: Pubkey,
+    pub enabled: bool,
+    pub confidence_threshold: u8,
+    pub reviews_submitted: u64,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ApproverSet {
+    #[max_len(10)]
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimApproval {
+    pub claim: Pubkey,
+    pub approver: Pubkey,
+    pub approved_at: i64,
     pub bump: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum ClaimType {
-    Theft,
-    Loss,
+pub enum LossType {
+    Total,
+    Partial,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
@@ -587,30 +1569,12 @@ pub enum AIDecision {
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum AssetType {
-    USDC,
-    SOL,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum CoverageType {
-    TheftOnly,
-    TheftAndLoss,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum PaymentFrequency {
-    Monthly,
-    Annual,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum PolicyStatus {
-    Active,
-    Expired,
-    Claimed,
-    Cancelled,
-    Suspended,
+pub enum ParamChange {
+    PayoutLimits {
+        max_auto_payout: u64,
+        daily_auto_payout_limit: u64,
+    },
+    TimelockDelay { new_delay: i64 },
 }
 
 #[event]
@@ -627,23 +1591,41 @@ pub struct ClaimSubmittedEvent {
     pub policy_id: u64,
     pub customer: Pubkey,
     pub claim_type: ClaimType,
+    pub loss_type: LossType,
+    pub claimed_amount: u64,
     pub claim_amount: u64,
+    pub deductible_amount: u64,
+    pub loss_date: i64,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct AIReviewCompletedEvent {
     pub claim_id: u64,
+    pub oracle: Pubkey,
     pub decision: AIDecision,
     pub confidence: u8,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ClaimAutoApprovedEvent {
+    pub claim_id: u64,
+    pub amount: u64,
+    pub confidence: u8,
+    pub daily_auto_paid: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ManualReviewCompletedEvent {
     pub claim_id: u64,
     pub reviewer: Pubkey,
     pub approved: bool,
+    pub claimed_amount: u64,
+    pub assessed_damage: u64,
+    pub approved_amount: u64,
+    pub reason_hash: [u8; 32],
     pub timestamp: i64,
 }
 
@@ -672,6 +1654,101 @@ pub struct PayoutLimitsUpdatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AIOracleRegisteredEvent {
+    pub oracle: Pubkey,
+    pub confidence_threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ApproverSetUpdatedEvent {
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimPayoutApprovedEvent {
+    pub claim_id: u64,
+    pub approver: Pubkey,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AIOracleUpdatedEvent {
+    pub oracle: Pubkey,
+    pub enabled: bool,
+    pub confidence_threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposedEvent {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelledEvent {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityUpdatedEvent {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeQueuedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeExecutedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeCancelledEvent {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdatedEvent {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseStateUpdatedEvent {
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub claims_paused: bool,
+    pub payouts_paused: bool,
+    pub timestamp: i64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Invalid amount")]
@@ -680,7 +1757,7 @@ pub enum ErrorCode {
     #[msg("Policy is not active")]
     PolicyNotActive,
 
-    #[msg("Policy has already been claimed")]
+    #[msg("Policy's aggregate limit is exhausted")]
     PolicyAlreadyClaimed,
 
     #[msg("Claim type not covered by policy")]
@@ -712,4 +1789,161 @@ pub enum ErrorCode {
 
     #[msg("Unauthorized")]
     Unauthorized,
-}
\ No newline at end of file
+
+    #[msg("Claimant does not match the claim")]
+    InvalidClaimant,
+
+    #[msg("Invalid program account")]
+    InvalidProgram,
+
+    #[msg("AI oracle is disabled")]
+    OracleDisabled,
+
+    #[msg("Invalid approver set")]
+    InvalidApproverSet,
+
+    #[msg("Signer is not in the approver set")]
+    NotAnApprover,
+
+    #[msg("Approver set has not been configured")]
+    ApproverSetNotConfigured,
+
+    #[msg("Invalid claim approval account")]
+    InvalidApproval,
+
+    #[msg("Not enough approvals for this claim")]
+    InsufficientApprovals,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Timelock delay out of bounds")]
+    InvalidTimelockDelay,
+
+    #[msg("Parameter change is not queued")]
+    ChangeNotQueued,
+
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Claim submissions are paused")]
+    ClaimsPaused,
+
+    #[msg("Claim payouts are paused")]
+    PayoutsPaused,
+
+    #[msg("Loss date is in the future")]
+    InvalidLossDate,
+
+    #[msg("Loss date is outside the policy coverage period")]
+    LossOutsideCoverage,
+
+    #[msg("Policy is suspended for non-payment")]
+    PolicySuspended,
+
+    #[msg("Loss occurred within the claims waiting period")]
+    LossInWaitingPeriod,
+
+    #[msg("Loss occurred after the premium paid-through date")]
+    LossInUnpaidPeriod,
+
+    #[msg("Claimed amount does not exceed the deductible")]
+    ClaimBelowDeductible,
+
+    #[msg("Approved amount exceeds the eligible payout")]
+    ApprovedAmountTooHigh,
+
+    #[msg("Assessed damage exceeds the claimed amount")]
+    DamageExceedsClaimedAmount,
+
+    #[msg("Policy's first premium has not been paid")]
+    PremiumNotPaid,
+
+    #[msg("AI review must return a decision")]
+    InvalidAIDecision,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocol_types::{CoverageType, PaymentFrequency};
+
+    #[test]
+    fn percentage_deductible() {
+        let result = calculate_deductible(DeductibleModel::Percentage, 10_000, 1_000, 0).unwrap();
+        assert_eq!(result, (1_000, 9_000));
+    }
+
+    #[test]
+    fn fixed_deductible() {
+        let model = DeductibleModel::Fixed { amount: 2_500 };
+        assert_eq!(calculate_deductible(model, 10_000, 1_000, 0).unwrap(), (2_500, 7_500));
+        // Never more than the claim itself
+        assert_eq!(calculate_deductible(model, 2_000, 1_000, 0).unwrap(), (2_000, 0));
+    }
+
+    #[test]
+    fn bounded_percentage_deductible() {
+        let model = DeductibleModel::PercentageWithBounds { min: 500, max: 3_000 };
+        assert_eq!(calculate_deductible(model, 2_000, 1_000, 0).unwrap(), (500, 1_500));
+        assert_eq!(calculate_deductible(model, 20_000, 1_000, 0).unwrap(), (2_000, 18_000));
+        assert_eq!(calculate_deductible(model, 100_000, 1_000, 0).unwrap(), (3_000, 97_000));
+    }
+
+    #[test]
+    fn disappearing_deductible() {
+        let model = DeductibleModel::Disappearing { reduction_bps_per_year: 250 };
+        assert_eq!(calculate_deductible(model, 10_000, 1_000, 0).unwrap(), (1_000, 9_000));
+        assert_eq!(calculate_deductible(model, 10_000, 1_000, 2).unwrap(), (500, 9_500));
+        assert_eq!(calculate_deductible(model, 10_000, 1_000, 10).unwrap(), (0, 10_000));
+    }
+
+    #[test]
+    fn payout_reflects_claim_free_years() {
+        let mut policy = Policy {
+            policy_id: 1,
+            product_id: 1,
+            customer: Pubkey::default(),
+            coverage_type: CoverageType::TheftOnly,
+            asset_details_hash: [0; 32],
+            insured_value: 100_000,
+            premium: 1_000,
+            deductible_bps: 1_000,
+            deductible_model: DeductibleModel::Disappearing { reduction_bps_per_year: 250 },
+            payment_frequency: PaymentFrequency::Annual,
+            start_date: 0,
+            expiry_date: 365 * 86_400,
+            term_start: 0,
+            status: PolicyStatus::Active,
+            total_paid: 1_000,
+            term_paid: 1_000,
+            term_fees_paid: 100,
+            next_payment_due: 365 * 86_400,
+            claim_count: 0,
+            per_claim_limit: 100_000,
+            aggregate_limit: 100_000,
+            remaining_aggregate: 100_000,
+            renewal_count: 0,
+            claim_free_years: 0,
+            cooling_off_ends: 0,
+            claims_waiting_ends: 0,
+            bump: 0,
+        };
+        assert_eq!(calculate_payout(&policy, 10_000).unwrap(), (1_000, 9_000));
+
+        policy.claim_free_years = 1;
+        assert_eq!(calculate_payout(&policy, 10_000).unwrap(), (750, 9_250));
+
+        policy.claim_free_years = 4;
+        assert_eq!(calculate_payout(&policy, 10_000).unwrap(), (0, 10_000));
+    }
+
+    #[test]
+    fn large_claims_do_not_overflow() {
+        let result = calculate_deductible(DeductibleModel::Percentage, u64::MAX, 10_000, 0).unwrap();
+        assert_eq!(result, (u64::MAX, 0));
+    }
+}
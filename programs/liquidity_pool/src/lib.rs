@@ -1,8 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn};
+pub use protocol_types::{AssetType, OraclePrice, ParamChangeStatus};
+use protocol_types::{DEFAULT_TIMELOCK_DELAY, MAX_TIMELOCK_DELAY, MIN_TIMELOCK_DELAY};
 
 declare_id!("LPoo1111111111111111111111111111111111111111");
 
+// Pool value is expressed in USDC base units (6 decimals); SOL balances are
+// converted through the configured SOL/USD oracle.
+const USDC_DECIMALS: i32 = 6;
+const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
+
+// Fixed-point scale of PoolState.acc_reward_per_share.
+const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
 #[program]
 pub mod liquidity_pool {
     use super::*;
@@ -15,6 +25,7 @@ pub mod liquidity_pool {
         
         let pool_state = &mut ctx.accounts.pool_state;
         pool_state.authority = ctx.accounts.authority.key();
+        pool_state.pending_authority = None;
         pool_state.pool_vault_usdc = ctx.accounts.pool_vault_usdc.key();
         pool_state.pool_vault_sol = ctx.accounts.pool_vault_sol.key();
         pool_state.lp_token_mint = ctx.accounts.lp_token_mint.key();
@@ -28,6 +39,26 @@ pub mod liquidity_pool {
         pool_state.last_interest_snapshot = Clock::get()?.unix_timestamp;
         pool_state.lp_fee_bps = lp_fee_bps;
         pool_state.scr_coverage_ratio = 10000;
+        pool_state.policy_manager = Pubkey::default();
+        pool_state.policy_manager_authority = Pubkey::default();
+        pool_state.claims_processor = Pubkey::default();
+        pool_state.claims_processor_authority = Pubkey::default();
+        pool_state.oracle_program = Pubkey::default();
+        pool_state.sol_price_feed = Pubkey::default();
+        pool_state.max_price_age = 0;
+        pool_state.max_price_conf_bps = 0;
+        pool_state.sol_price = 0;
+        pool_state.sol_price_updated_at = 0;
+        pool_state.reward_vault = Pubkey::default();
+        pool_state.acc_reward_per_share = 0;
+        pool_state.total_rewards_distributed = 0;
+        pool_state.total_rewards_claimed = 0;
+        pool_state.guardian = ctx.accounts.authority.key();
+        pool_state.paused = false;
+        pool_state.deposits_paused = false;
+        pool_state.withdrawals_paused = false;
+        pool_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        pool_state.param_change_count = 0;
         pool_state.bump = ctx.bumps.pool_state;
 
         msg!("Pool initialized with LP fee: {}bps", lp_fee_bps);
@@ -38,11 +69,17 @@ pub mod liquidity_pool {
         ctx: Context<DepositLPUSDC>,
         amount: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.pool_state.deposits_paused, ErrorCode::DepositsPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let pool_state = &mut ctx.accounts.pool_state;
         let lp_position = &mut ctx.accounts.lp_position;
-        
+
+        // A pool holding no SOL can be valued without the oracle
+        if pool_state.total_capital_sol > 0 {
+            refresh_sol_price(pool_state, &ctx.accounts.sol_price_feed)?;
+        }
         let lp_tokens_to_mint = calculate_lp_tokens_to_mint(amount, pool_state)?;
         
         let cpi_ctx = CpiContext::new(
@@ -55,10 +92,7 @@ pub mod liquidity_pool {
         );
         token::transfer(cpi_ctx, amount)?;
 
-        let seeds = &[
-            b"pool_state",
-            &[pool_state.bump],
-        ];
+        let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
         let signer = &[&seeds[..]];
 
         let mint_ctx = CpiContext::new_with_signer(
@@ -82,9 +116,11 @@ pub mod liquidity_pool {
         pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
 
         lp_position.owner = ctx.accounts.depositor.key();
+        settle_rewards(pool_state, lp_position)?;
         lp_position.lp_tokens = lp_position.lp_tokens
             .checked_add(lp_tokens_to_mint)
             .ok_or(ErrorCode::Overflow)?;
+        checkpoint_rewards(pool_state, lp_position)?;
         lp_position.usdc_deposited = lp_position.usdc_deposited
             .checked_add(amount)
             .ok_or(ErrorCode::Overflow)?;
@@ -109,12 +145,16 @@ pub mod liquidity_pool {
         ctx: Context<DepositLPSOL>,
         amount: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.pool_state.deposits_paused, ErrorCode::DepositsPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let pool_state = &mut ctx.accounts.pool_state;
         let lp_position = &mut ctx.accounts.lp_position;
-        
-        let lp_tokens_to_mint = calculate_lp_tokens_to_mint(amount, pool_state)?;
+
+        refresh_sol_price(pool_state, &ctx.accounts.sol_price_feed)?;
+        let deposit_value = sol_to_usdc(amount, pool_state.sol_price)?;
+        let lp_tokens_to_mint = calculate_lp_tokens_to_mint(deposit_value, pool_state)?;
         
         let ix = anchor_lang::solana_program::system_instruction::transfer(
             &ctx.accounts.depositor.key(),
@@ -129,10 +169,7 @@ pub mod liquidity_pool {
             ],
         )?;
 
-        let seeds = &[
-            b"pool_state",
-            &[pool_state.bump],
-        ];
+        let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
         let signer = &[&seeds[..]];
 
         let mint_ctx = CpiContext::new_with_signer(
@@ -156,9 +193,11 @@ pub mod liquidity_pool {
         pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
 
         lp_position.owner = ctx.accounts.depositor.key();
+        settle_rewards(pool_state, lp_position)?;
         lp_position.lp_tokens = lp_position.lp_tokens
             .checked_add(lp_tokens_to_mint)
             .ok_or(ErrorCode::Overflow)?;
+        checkpoint_rewards(pool_state, lp_position)?;
         lp_position.sol_deposited = lp_position.sol_deposited
             .checked_add(amount)
             .ok_or(ErrorCode::Overflow)?;
@@ -183,6 +222,8 @@ pub mod liquidity_pool {
         ctx: Context<WithdrawLPUSDC>,
         lp_tokens: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.pool_state.withdrawals_paused, ErrorCode::WithdrawalsPaused);
         require!(lp_tokens > 0, ErrorCode::InvalidAmount);
         
         let pool_state = &mut ctx.accounts.pool_state;
@@ -193,6 +234,9 @@ pub mod liquidity_pool {
             ErrorCode::InsufficientLPTokens
         );
 
+        if pool_state.total_capital_sol > 0 {
+            refresh_sol_price(pool_state, &ctx.accounts.sol_price_feed)?;
+        }
         let (net_amount, fee) = calculate_withdrawal_amount(lp_tokens, pool_state)?;
 
         require!(
@@ -210,10 +254,7 @@ pub mod liquidity_pool {
         );
         token::burn(burn_ctx, lp_tokens)?;
 
-        let seeds = &[
-            b"pool_state",
-            &[pool_state.bump],
-        ];
+        let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
         let signer = &[&seeds[..]];
 
         let transfer_ctx = CpiContext::new_with_signer(
@@ -236,9 +277,11 @@ pub mod liquidity_pool {
 
         pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
 
+        settle_rewards(pool_state, lp_position)?;
         lp_position.lp_tokens = lp_position.lp_tokens
             .checked_sub(lp_tokens)
             .ok_or(ErrorCode::Underflow)?;
+        checkpoint_rewards(pool_state, lp_position)?;
         lp_position.last_withdrawal = Clock::get()?.unix_timestamp;
 
         emit!(WithdrawalEvent {
@@ -257,6 +300,8 @@ pub mod liquidity_pool {
         ctx: Context<WithdrawLPSOL>,
         lp_tokens: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.pool_state.withdrawals_paused, ErrorCode::WithdrawalsPaused);
         require!(lp_tokens > 0, ErrorCode::InvalidAmount);
         
         let pool_state = &mut ctx.accounts.pool_state;
@@ -267,7 +312,10 @@ pub mod liquidity_pool {
             ErrorCode::InsufficientLPTokens
         );
 
-        let (net_amount, fee) = calculate_withdrawal_amount(lp_tokens, pool_state)?;
+        refresh_sol_price(pool_state, &ctx.accounts.sol_price_feed)?;
+        let (net_value, fee_value) = calculate_withdrawal_amount(lp_tokens, pool_state)?;
+        let net_amount = usdc_to_sol(net_value, pool_state.sol_price)?;
+        let fee = usdc_to_sol(fee_value, pool_state.sol_price)?;
 
         require!(
             ctx.accounts.pool_vault_sol.lamports() >= net_amount,
@@ -284,19 +332,25 @@ pub mod liquidity_pool {
         );
         token::burn(burn_ctx, lp_tokens)?;
 
-        **ctx.accounts.pool_vault_sol.to_account_info().try_borrow_mut_lamports()? = ctx.accounts
-            .pool_vault_sol
-            .to_account_info()
-            .lamports()
-            .checked_sub(net_amount)
-            .ok_or(ErrorCode::Underflow)?;
+        // The SOL vault is a system-owned PDA, so lamports have to leave it
+        // through a signed system transfer rather than a direct debit.
+        let vault_seeds: [&[u8]; 2] = [b"pool_vault_sol", &[ctx.bumps.pool_vault_sol]];
+        let vault_signer = &[&vault_seeds[..]];
 
-        **ctx.accounts.withdrawer.to_account_info().try_borrow_mut_lamports()? = ctx.accounts
-            .withdrawer
-            .to_account_info()
-            .lamports()
-            .checked_add(net_amount)
-            .ok_or(ErrorCode::Overflow)?;
+        let ix = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.pool_vault_sol.key(),
+            &ctx.accounts.withdrawer.key(),
+            net_amount,
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.pool_vault_sol.to_account_info(),
+                ctx.accounts.withdrawer.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            vault_signer,
+        )?;
 
         pool_state.total_capital_sol = pool_state.total_capital_sol
             .checked_sub(net_amount)
@@ -307,9 +361,11 @@ pub mod liquidity_pool {
 
         pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
 
+        settle_rewards(pool_state, lp_position)?;
         lp_position.lp_tokens = lp_position.lp_tokens
             .checked_sub(lp_tokens)
             .ok_or(ErrorCode::Underflow)?;
+        checkpoint_rewards(pool_state, lp_position)?;
         lp_position.last_withdrawal = Clock::get()?.unix_timestamp;
 
         emit!(WithdrawalEvent {
@@ -328,6 +384,8 @@ pub mod liquidity_pool {
         ctx: Context<RecordPremium>,
         amount: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+
         let pool_state = &mut ctx.accounts.pool_state;
 
         pool_state.total_premiums_collected = pool_state.total_premiums_collected
@@ -346,26 +404,75 @@ pub mod liquidity_pool {
         Ok(())
     }
 
+    pub fn refund_premium(
+        ctx: Context<RefundPremium>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            ctx.accounts.pool_vault_usdc.amount >= amount,
+            ErrorCode::InsufficientPoolLiquidity
+        );
+
+        let pool_state = &mut ctx.accounts.pool_state;
+
+        let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
+        let signer = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_vault_usdc.to_account_info(),
+                to: ctx.accounts.recipient_usdc.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)?;
+
+        pool_state.total_premiums_collected = pool_state.total_premiums_collected
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+
+        pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
+
+        emit!(PremiumRefundedEvent {
+            amount,
+            recipient: ctx.accounts.recipient_usdc.key(),
+            total_premiums: pool_state.total_premiums_collected,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Refunded premium: {}", amount);
+        Ok(())
+    }
+
+    /// `amount` is the claim value in USDC base units; SOL payouts are
+    /// converted at a freshly checked oracle price.
     pub fn execute_payout(
         ctx: Context<ExecutePayout>,
         amount: u64,
         asset_type: AssetType,
     ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let pool_state = &mut ctx.accounts.pool_state;
 
+        if asset_type == AssetType::SOL {
+            refresh_sol_price(pool_state, &ctx.accounts.sol_price_feed)?;
+        }
+        let paid = book_payout(pool_state, amount, asset_type)?;
+
         match asset_type {
             AssetType::USDC => {
                 require!(
-                    ctx.accounts.pool_vault_usdc.amount >= amount,
+                    ctx.accounts.pool_vault_usdc.amount >= paid,
                     ErrorCode::InsufficientPoolLiquidity
                 );
 
-                let seeds = &[
-                    b"pool_state",
-                    &[pool_state.bump],
-                ];
+                let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
                 let signer = &[&seeds[..]];
 
                 let transfer_ctx = CpiContext::new_with_signer(
@@ -377,42 +484,36 @@ pub mod liquidity_pool {
                     },
                     signer,
                 );
-                token::transfer(transfer_ctx, amount)?;
-
-                pool_state.total_capital_usdc = pool_state.total_capital_usdc
-                    .checked_sub(amount)
-                    .ok_or(ErrorCode::Underflow)?;
+                token::transfer(transfer_ctx, paid)?;
             }
             AssetType::SOL => {
                 require!(
-                    ctx.accounts.pool_vault_sol.lamports() >= amount,
+                    ctx.accounts.pool_vault_sol.lamports() >= paid,
                     ErrorCode::InsufficientPoolLiquidity
                 );
 
-                **ctx.accounts.pool_vault_sol.to_account_info().try_borrow_mut_lamports()? = ctx.accounts
-                    .pool_vault_sol
-                    .to_account_info()
-                    .lamports()
-                    .checked_sub(amount)
-                    .ok_or(ErrorCode::Underflow)?;
-
-                **ctx.accounts.claimant.to_account_info().try_borrow_mut_lamports()? = ctx.accounts
-                    .claimant
-                    .to_account_info()
-                    .lamports()
-                    .checked_add(amount)
-                    .ok_or(ErrorCode::Overflow)?;
-
-                pool_state.total_capital_sol = pool_state.total_capital_sol
-                    .checked_sub(amount)
-                    .ok_or(ErrorCode::Underflow)?;
+                // The SOL vault is a system-owned PDA, so lamports have to leave it
+                // through a signed system transfer rather than a direct debit.
+                let vault_seeds: [&[u8]; 2] = [b"pool_vault_sol", &[ctx.bumps.pool_vault_sol]];
+                let vault_signer = &[&vault_seeds[..]];
+
+                let ix = anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.pool_vault_sol.key(),
+                    &ctx.accounts.claimant.key(),
+                    paid,
+                );
+                anchor_lang::solana_program::program::invoke_signed(
+                    &ix,
+                    &[
+                        ctx.accounts.pool_vault_sol.to_account_info(),
+                        ctx.accounts.claimant.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                    vault_signer,
+                )?;
             }
         }
 
-        pool_state.total_claims_paid = pool_state.total_claims_paid
-            .checked_add(amount)
-            .ok_or(ErrorCode::Overflow)?;
-
         pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
 
         emit!(PayoutEvent {
@@ -443,7 +544,7 @@ pub mod liquidity_pool {
         snapshot.epoch = epoch;
         snapshot.timestamp = Clock::get()?.unix_timestamp;
         snapshot.total_capital = pool_state.total_capital_usdc
-            .checked_add(pool_state.total_capital_sol)
+            .checked_add(sol_to_usdc(pool_state.total_capital_sol, pool_state.sol_price)?)
             .ok_or(ErrorCode::Overflow)?;
         snapshot.interest_rate_bps = interest_rate_bps;
         snapshot.interest_accrued = interest_accrued;
@@ -468,7 +569,7 @@ pub mod liquidity_pool {
     }
 
     pub fn update_scr(
-        ctx: Context<UpdateSCR>,
+        ctx: Context<QueueParamChange>,
         new_scr: u64,
     ) -> Result<()> {
         require!(
@@ -476,175 +577,768 @@ pub mod liquidity_pool {
             ErrorCode::Unauthorized
         );
 
-        let pool_state = &mut ctx.accounts.pool_state;
-        pool_state.statutory_capital_required = new_scr;
-        pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
+        let change = ParamChange::SCR { new_scr };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.pool_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
 
-        emit!(SCRUpdateEvent {
-            new_scr,
-            coverage_ratio: pool_state.scr_coverage_ratio,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    /// Price feeds value every LP position, so switching them waits out the
+    /// timelock like any other parameter change.
+    pub fn configure_oracle(
+        ctx: Context<QueueParamChange>,
+        oracle_program: Pubkey,
+        sol_price_feed: Pubkey,
+        max_price_age: i64,
+        max_price_conf_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::Oracle {
+            oracle_program,
+            sol_price_feed,
+            max_price_age,
+            max_price_conf_bps,
+        };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.pool_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    /// Trusted callers can move pool funds, so changing them is timelocked.
+    pub fn set_trusted_callers(
+        ctx: Context<QueueParamChange>,
+        policy_manager: Pubkey,
+        claims_processor: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TrustedCallers {
+            policy_manager,
+            claims_processor,
+        };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.pool_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn initialize_reward_vault(
+        ctx: Context<InitializeRewardVault>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pool_state = &mut ctx.accounts.pool_state;
+        pool_state.reward_vault = ctx.accounts.reward_vault.key();
 
-        msg!("Updated SCR to: {}, coverage ratio: {}bps", new_scr, pool_state.scr_coverage_ratio);
+        msg!("Reward vault initialized: {}", pool_state.reward_vault);
         Ok(())
     }
 
     pub fn distribute_rewards(
         ctx: Context<DistributeRewards>,
+        amount: u64,
     ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
         require!(
             ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
             ErrorCode::Unauthorized
         );
 
-        let pool_state = &ctx.accounts.pool_state;
-        let lp_position = &mut ctx.accounts.lp_position;
+        let pool_state = &mut ctx.accounts.pool_state;
+        require!(pool_state.total_lp_supply > 0, ErrorCode::NoLiquidityProviders);
 
+        // Only profit that has not been handed out before can be distributed,
+        // so repeated calls never pay the same profit twice.
         let net_profit = calculate_net_profit(pool_state)?;
         let lp_share = net_profit
             .checked_mul(10000 - pool_state.lp_fee_bps as u64)
             .ok_or(ErrorCode::Overflow)?
             .checked_div(10000)
             .ok_or(ErrorCode::DivisionByZero)?;
+        let distributable = lp_share.saturating_sub(pool_state.total_rewards_distributed);
+        require!(amount <= distributable, ErrorCode::ExceedsDistributableProfit);
+
+        require!(
+            ctx.accounts.pool_vault_usdc.amount >= amount,
+            ErrorCode::InsufficientPoolLiquidity
+        );
+
+        let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
+        let signer = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_vault_usdc.to_account_info(),
+                to: ctx.accounts.reward_vault.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)?;
 
-        let lp_reward = lp_share
-            .checked_mul(lp_position.lp_tokens)
+        let reward_per_share = (amount as u128)
+            .checked_mul(REWARD_PRECISION)
             .ok_or(ErrorCode::Overflow)?
-            .checked_div(pool_state.total_lp_supply)
+            .checked_div(pool_state.total_lp_supply as u128)
             .ok_or(ErrorCode::DivisionByZero)?;
-
-        lp_position.rewards_earned = lp_position.rewards_earned
-            .checked_add(lp_reward)
+        pool_state.acc_reward_per_share = pool_state.acc_reward_per_share
+            .checked_add(reward_per_share)
+            .ok_or(ErrorCode::Overflow)?;
+        pool_state.total_rewards_distributed = pool_state.total_rewards_distributed
+            .checked_add(amount)
             .ok_or(ErrorCode::Overflow)?;
 
+        pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
+
         emit!(RewardDistributionEvent {
-            lp_owner: lp_position.owner,
-            reward_amount: lp_reward,
+            amount,
+            acc_reward_per_share: pool_state.acc_reward_per_share,
+            total_distributed: pool_state.total_rewards_distributed,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Distributed {} rewards to LP", lp_reward);
+        msg!("Distributed {} rewards to LPs", amount);
         Ok(())
     }
-}
 
-fn calculate_lp_tokens_to_mint(deposit_amount: u64, pool_state: &PoolState) -> Result<u64> {
-    if pool_state.total_lp_supply == 0 {
-        Ok(deposit_amount)
-    } else {
-        let pool_value = calculate_pool_value(pool_state)?;
-        
-        let lp_tokens = (deposit_amount as u128)
-            .checked_mul(pool_state.total_lp_supply as u128)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(pool_value as u128)
-            .ok_or(ErrorCode::DivisionByZero)?;
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.pool_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.pool_state.withdrawals_paused, ErrorCode::WithdrawalsPaused);
 
-        Ok(lp_tokens as u64)
-    }
-}
+        let pool_state = &mut ctx.accounts.pool_state;
+        let lp_position = &mut ctx.accounts.lp_position;
 
-fn calculate_withdrawal_amount(lp_tokens: u64, pool_state: &PoolState) -> Result<(u64, u64)> {
-    let pool_value = calculate_pool_value(pool_state)?;
-    
-    let gross_amount = (lp_tokens as u128)
-        .checked_mul(pool_value as u128)
-        .ok_or(ErrorCode::Overflow)?
-        .checked_div(pool_state.total_lp_supply as u128)
-        .ok_or(ErrorCode::DivisionByZero)?;
+        settle_rewards(pool_state, lp_position)?;
+        checkpoint_rewards(pool_state, lp_position)?;
 
-    let gross_amount = gross_amount as u64;
-    
-    let fee_bps = calculate_withdrawal_fee(pool_state.scr_coverage_ratio);
-    let fee = gross_amount
-        .checked_mul(fee_bps as u64)
-        .ok_or(ErrorCode::Overflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::DivisionByZero)?;
-    
-    let net_amount = gross_amount
-        .checked_sub(fee)
-        .ok_or(ErrorCode::Underflow)?;
-    
-    Ok((net_amount, fee))
-}
+        let amount = lp_position.pending_rewards;
+        require!(amount > 0, ErrorCode::NoRewardsToClaim);
 
-fn calculate_pool_value(pool_state: &PoolState) -> Result<u64> {
-    let value = pool_state.total_capital_usdc
-        .checked_add(pool_state.total_capital_sol)
-        .ok_or(ErrorCode::Overflow)?
-        .checked_add(pool_state.total_premiums_collected)
-        .ok_or(ErrorCode::Overflow)?
-        .checked_add(pool_state.total_interest_earned)
-        .ok_or(ErrorCode::Overflow)?
-        .checked_sub(pool_state.total_claims_paid)
-        .ok_or(ErrorCode::Underflow)?;
-    
-    Ok(value)
-}
+        let seeds: [&[u8]; 2] = [b"pool_state", &[pool_state.bump]];
+        let signer = &[&seeds[..]];
 
-fn calculate_scr_coverage(pool_state: &PoolState) -> u16 {
-    if pool_state.statutory_capital_required == 0 {
-        return 10000;
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                to: ctx.accounts.owner_usdc.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)?;
+
+        lp_position.pending_rewards = 0;
+        lp_position.rewards_claimed = lp_position.rewards_claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        pool_state.total_rewards_claimed = pool_state.total_rewards_claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(RewardsClaimedEvent {
+            lp_owner: lp_position.owner,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("LP {} claimed {} rewards", lp_position.owner, amount);
+        Ok(())
     }
 
-    let total_capital = pool_state.total_capital_usdc
-        .saturating_add(pool_state.total_capital_sol)
-        .saturating_add(pool_state.total_premiums_collected)
-        .saturating_add(pool_state.total_interest_earned)
-        .saturating_sub(pool_state.total_claims_paid);
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
 
-    let ratio = (total_capital as u128)
-        .saturating_mul(10000)
-        .saturating_div(pool_state.statutory_capital_required as u128);
+        // The new key only takes over once it signs accept_authority, so a
+        // mistyped address can be cancelled instead of locking out the admin.
+        let pool_state = &mut ctx.accounts.pool_state;
+        pool_state.pending_authority = Some(new_authority);
 
-    ratio.min(u16::MAX as u128) as u16
-}
+        emit!(AuthorityTransferProposedEvent {
+            current_authority: pool_state.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-fn calculate_withdrawal_fee(coverage_ratio: u16) -> u16 {
-    match coverage_ratio {
-        0..=5000 => 10000,
-        5001..=7000 => 5000,
-        7001..=9000 => 2000,
-        9001..=11000 => 500,
-        11001..=13000 => 100,
-        _ => 0,
+        msg!("Authority transfer proposed: {} -> {}", pool_state.authority, new_authority);
+        Ok(())
     }
-}
 
-fn calculate_net_profit(pool_state: &PoolState) -> Result<u64> {
-    let gross_revenue = pool_state.total_premiums_collected
-        .checked_add(pool_state.total_interest_earned)
-        .ok_or(ErrorCode::Overflow)?;
-    
-    let net_profit = gross_revenue
-        .checked_sub(pool_state.total_claims_paid)
-        .ok_or(ErrorCode::Underflow)?;
-    
-    Ok(net_profit)
-}
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        let pool_state = &mut ctx.accounts.pool_state;
+        let new_authority = pool_state.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
+        require!(
+            ctx.accounts.new_authority.key() == new_authority,
+            ErrorCode::Unauthorized
+        );
 
-#[derive(Accounts)]
-pub struct InitializePool<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + PoolState::INIT_SPACE,
-        seeds = [b"pool_state"],
-        bump
-    )]
-    pub pool_state: Account<'info, PoolState>,
+        let old_authority = pool_state.authority;
+        pool_state.authority = new_authority;
+        pool_state.pending_authority = None;
 
-    #[account(
-        init,
-        payer = authority,
-        token::mint = usdc_mint,
-        token::authority = pool_state,
-        seeds = [b"pool_vault_usdc"],
-        bump
-    )]
-    pub pool_vault_usdc: Account<'info, TokenAccount>,
+        emit!(AuthorityUpdatedEvent {
+            old_authority,
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority updated: {} -> {}", old_authority, new_authority);
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(
+        ctx: Context<CancelAuthorityTransfer>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pool_state = &mut ctx.accounts.pool_state;
+        let cancelled_authority = pool_state.pending_authority.take().ok_or(ErrorCode::NoPendingAuthority)?;
+
+        emit!(AuthorityTransferCancelledEvent {
+            authority: pool_state.authority,
+            cancelled_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transfer to {} cancelled", cancelled_authority);
+        Ok(())
+    }
+
+    pub fn update_timelock_delay(
+        ctx: Context<QueueParamChange>,
+        new_delay: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TimelockDelay { new_delay };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.pool_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    /// Applies a queued change once its ETA has passed. Permissionless, since
+    /// the change itself was already authorized when it was queued.
+    pub fn execute_parameter_change(
+        ctx: Context<ExecuteParameterChange>,
+    ) -> Result<()> {
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= pending_change.eta, ErrorCode::TimelockNotElapsed);
+
+        validate_param_change(&pending_change.change)?;
+        apply_param_change(&mut ctx.accounts.pool_state, pending_change.change)?;
+        pending_change.status = ParamChangeStatus::Executed;
+
+        emit!(ParamChangeExecutedEvent {
+            id: pending_change.id,
+            change: pending_change.change,
+            timestamp: now,
+        });
+
+        msg!("Parameter change {} executed", pending_change.id);
+        Ok(())
+    }
+
+    pub fn cancel_parameter_change(
+        ctx: Context<CancelParameterChange>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        pending_change.status = ParamChangeStatus::Cancelled;
+
+        emit!(ParamChangeCancelledEvent {
+            id: pending_change.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Parameter change {} cancelled", pending_change.id);
+        Ok(())
+    }
+
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pool_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pool_state = &mut ctx.accounts.pool_state;
+        let old_guardian = pool_state.guardian;
+        pool_state.guardian = new_guardian;
+
+        emit!(GuardianUpdatedEvent {
+            old_guardian,
+            new_guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Guardian updated: {} -> {}", old_guardian, new_guardian);
+        Ok(())
+    }
+
+    /// Circuit breaker. Only the guardian can flip these, so a pause doesn't
+    /// have to wait on the authority key or the timelock.
+    pub fn set_pause_state(
+        ctx: Context<SetPauseState>,
+        paused: bool,
+        deposits_paused: bool,
+        withdrawals_paused: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.guardian.key() == ctx.accounts.pool_state.guardian,
+            ErrorCode::Unauthorized
+        );
+
+        let pool_state = &mut ctx.accounts.pool_state;
+        pool_state.paused = paused;
+        pool_state.deposits_paused = deposits_paused;
+        pool_state.withdrawals_paused = withdrawals_paused;
+
+        emit!(PauseStateUpdatedEvent {
+            guardian: ctx.accounts.guardian.key(),
+            paused,
+            deposits_paused,
+            withdrawals_paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Pause state updated: paused={}, deposits_paused={}, withdrawals_paused={}", paused, deposits_paused, withdrawals_paused);
+        Ok(())
+    }
+}
+
+/// Stores `change` as the next pending change, executable after the current timelock delay
+fn queue_param_change(
+    pool_state: &mut PoolState,
+    pending_change: &mut PendingChange,
+    change: ParamChange,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    pool_state.param_change_count = pool_state.param_change_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    pending_change.id = pool_state.param_change_count;
+    pending_change.change = change;
+    pending_change.queued_at = now;
+    pending_change.eta = now
+        .checked_add(pool_state.timelock_delay)
+        .ok_or(ErrorCode::Overflow)?;
+    pending_change.status = ParamChangeStatus::Queued;
+    pending_change.bump = bump;
+
+    emit!(ParamChangeQueuedEvent {
+        id: pending_change.id,
+        change,
+        eta: pending_change.eta,
+        timestamp: now,
+    });
+
+    msg!("Parameter change {} queued: {:?}, executable at {}", pending_change.id, change, pending_change.eta);
+    Ok(())
+}
+
+/// Bounds checks shared by queueing and execution
+fn validate_param_change(change: &ParamChange) -> Result<()> {
+    match change {
+        ParamChange::TimelockDelay { new_delay } => {
+            require!(
+                (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(new_delay),
+                ErrorCode::InvalidTimelockDelay
+            );
+        }
+        ParamChange::Oracle { max_price_age, max_price_conf_bps, .. } => {
+            require!(*max_price_age > 0, ErrorCode::InvalidOracleConfig);
+            require!(
+                *max_price_conf_bps > 0 && *max_price_conf_bps <= 10000,
+                ErrorCode::InvalidOracleConfig
+            );
+        }
+        ParamChange::SCR { .. } | ParamChange::TrustedCallers { .. } => {}
+    }
+    Ok(())
+}
+
+fn apply_param_change(pool_state: &mut PoolState, change: ParamChange) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    match change {
+        ParamChange::SCR { new_scr } => {
+            pool_state.statutory_capital_required = new_scr;
+            pool_state.scr_coverage_ratio = calculate_scr_coverage(pool_state);
+
+            emit!(SCRUpdateEvent {
+                new_scr,
+                coverage_ratio: pool_state.scr_coverage_ratio,
+                timestamp,
+            });
+
+            msg!("Updated SCR to: {}, coverage ratio: {}bps", new_scr, pool_state.scr_coverage_ratio);
+        }
+        ParamChange::Oracle { oracle_program, sol_price_feed, max_price_age, max_price_conf_bps } => {
+            pool_state.oracle_program = oracle_program;
+            pool_state.sol_price_feed = sol_price_feed;
+            pool_state.max_price_age = max_price_age;
+            pool_state.max_price_conf_bps = max_price_conf_bps;
+
+            emit!(OracleConfiguredEvent {
+                oracle_program,
+                sol_price_feed,
+                max_price_age,
+                max_price_conf_bps,
+                timestamp,
+            });
+
+            msg!("Oracle configured: feed={}, max_age={}s, max_conf={}bps", sol_price_feed, max_price_age, max_price_conf_bps);
+        }
+        ParamChange::TrustedCallers { policy_manager, claims_processor } => {
+            // Sibling programs sign their CPIs with a PDA derived from their own
+            // program ID, so only those exact addresses are accepted as callers.
+            pool_state.policy_manager = policy_manager;
+            pool_state.policy_manager_authority =
+                Pubkey::find_program_address(&[b"policy_authority"], &policy_manager).0;
+            pool_state.claims_processor = claims_processor;
+            pool_state.claims_processor_authority =
+                Pubkey::find_program_address(&[b"claims_authority"], &claims_processor).0;
+
+            emit!(TrustedCallersUpdatedEvent {
+                policy_manager,
+                policy_manager_authority: pool_state.policy_manager_authority,
+                claims_processor,
+                claims_processor_authority: pool_state.claims_processor_authority,
+                timestamp,
+            });
+
+            msg!("Trusted callers updated: policy_manager={}, claims_processor={}", policy_manager, claims_processor);
+        }
+        ParamChange::TimelockDelay { new_delay } => {
+            let old_delay = pool_state.timelock_delay;
+            pool_state.timelock_delay = new_delay;
+
+            emit!(TimelockDelayUpdatedEvent {
+                old_delay,
+                new_delay,
+                timestamp,
+            });
+
+            msg!("Timelock delay updated: {}s -> {}s", old_delay, new_delay);
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves rewards accrued since the position's last checkpoint into
+/// `pending_rewards`. Must run before `lp_tokens` changes.
+fn settle_rewards(pool_state: &PoolState, lp_position: &mut LPPosition) -> Result<()> {
+    let accrued = (lp_position.lp_tokens as u128)
+        .checked_mul(pool_state.acc_reward_per_share)
+        .ok_or(ErrorCode::Overflow)?
+        / REWARD_PRECISION;
+    let pending = accrued
+        .checked_sub(lp_position.reward_debt)
+        .ok_or(ErrorCode::Underflow)?;
+    let pending = u64::try_from(pending).map_err(|_| ErrorCode::Overflow)?;
+
+    lp_position.pending_rewards = lp_position.pending_rewards
+        .checked_add(pending)
+        .ok_or(ErrorCode::Overflow)?;
+    lp_position.rewards_earned = lp_position.rewards_earned
+        .checked_add(pending)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+/// Records the accumulator value the position has been settled up to. Must
+/// run after `lp_tokens` changes.
+fn checkpoint_rewards(pool_state: &PoolState, lp_position: &mut LPPosition) -> Result<()> {
+    lp_position.reward_debt = (lp_position.lp_tokens as u128)
+        .checked_mul(pool_state.acc_reward_per_share)
+        .ok_or(ErrorCode::Overflow)?
+        / REWARD_PRECISION;
+    Ok(())
+}
+
+fn calculate_lp_tokens_to_mint(deposit_amount: u64, pool_state: &PoolState) -> Result<u64> {
+    if pool_state.total_lp_supply == 0 {
+        Ok(deposit_amount)
+    } else {
+        let pool_value = calculate_pool_value(pool_state)?;
+        
+        let lp_tokens = (deposit_amount as u128)
+            .checked_mul(pool_state.total_lp_supply as u128)
+            .ok_or(ErrorCode::Overflow)?
+            .checked_div(pool_value as u128)
+            .ok_or(ErrorCode::DivisionByZero)?;
+
+        Ok(lp_tokens as u64)
+    }
+}
+
+fn calculate_withdrawal_amount(lp_tokens: u64, pool_state: &PoolState) -> Result<(u64, u64)> {
+    let pool_value = calculate_pool_value(pool_state)?;
+    
+    let gross_amount = (lp_tokens as u128)
+        .checked_mul(pool_value as u128)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(pool_state.total_lp_supply as u128)
+        .ok_or(ErrorCode::DivisionByZero)?;
+
+    let gross_amount = gross_amount as u64;
+    
+    let fee_bps = calculate_withdrawal_fee(pool_state.scr_coverage_ratio);
+    let fee = gross_amount
+        .checked_mul(fee_bps as u64)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::DivisionByZero)?;
+    
+    let net_amount = gross_amount
+        .checked_sub(fee)
+        .ok_or(ErrorCode::Underflow)?;
+    
+    Ok((net_amount, fee))
+}
+
+/// Takes a payout worth `amount` USDC out of pool capital and returns what
+/// leaves the vault, in the payout asset's own units. `total_claims_paid` is
+/// only a running total; the capital figures already reflect each payout.
+fn book_payout(pool_state: &mut PoolState, amount: u64, asset_type: AssetType) -> Result<u64> {
+    let paid = match asset_type {
+        AssetType::USDC => {
+            pool_state.total_capital_usdc = pool_state.total_capital_usdc
+                .checked_sub(amount)
+                .ok_or(ErrorCode::Underflow)?;
+            amount
+        }
+        AssetType::SOL => {
+            let lamports = usdc_to_sol(amount, pool_state.sol_price)?;
+            require!(lamports > 0, ErrorCode::InvalidAmount);
+            pool_state.total_capital_sol = pool_state.total_capital_sol
+                .checked_sub(lamports)
+                .ok_or(ErrorCode::Underflow)?;
+            lamports
+        }
+    };
+
+    pool_state.total_claims_paid = pool_state.total_claims_paid
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(paid)
+}
+
+fn calculate_pool_value(pool_state: &PoolState) -> Result<u64> {
+    let sol_value = sol_to_usdc(pool_state.total_capital_sol, pool_state.sol_price)?;
+
+    let value = pool_state.total_capital_usdc
+        .checked_add(sol_value)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_add(pool_state.total_premiums_collected)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_add(pool_state.total_interest_earned)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_sub(pool_state.total_rewards_distributed)
+        .ok_or(ErrorCode::Underflow)?;
+    
+    Ok(value)
+}
+
+fn calculate_scr_coverage(pool_state: &PoolState) -> u16 {
+    if pool_state.statutory_capital_required == 0 {
+        return 10000;
+    }
+
+    // SOL that cannot be valued counts for nothing rather than everything
+    let sol_value = sol_to_usdc(pool_state.total_capital_sol, pool_state.sol_price)
+        .unwrap_or(0);
+
+    let total_capital = pool_state.total_capital_usdc
+        .saturating_add(sol_value)
+        .saturating_add(pool_state.total_premiums_collected)
+        .saturating_add(pool_state.total_interest_earned)
+        .saturating_sub(pool_state.total_rewards_distributed);
+
+    let ratio = (total_capital as u128)
+        .saturating_mul(10000)
+        .saturating_div(pool_state.statutory_capital_required as u128);
+
+    ratio.min(u16::MAX as u128) as u16
+}
+
+/// Reads the SOL/USD feed, rejecting stale or low-confidence prices, and
+/// returns the price of one SOL in USDC base units.
+fn load_sol_price(price_feed: &AccountInfo, pool_state: &PoolState) -> Result<u64> {
+    require!(
+        price_feed.key() == pool_state.sol_price_feed && *price_feed.owner == pool_state.oracle_program,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let data = price_feed.try_borrow_data()?;
+    require!(data.len() >= 8 + OraclePrice::INIT_SPACE, ErrorCode::InvalidPriceFeed);
+    let oracle_price = OraclePrice::deserialize(&mut &data[8..])
+        .map_err(|_| ErrorCode::InvalidPriceFeed)?;
+
+    require!(oracle_price.price > 0, ErrorCode::InvalidPrice);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now.saturating_sub(oracle_price.publish_time) <= pool_state.max_price_age,
+        ErrorCode::StalePrice
+    );
+
+    require!(
+        (oracle_price.conf as u128).saturating_mul(10000)
+            <= (oracle_price.price as u128).saturating_mul(pool_state.max_price_conf_bps as u128),
+        ErrorCode::PriceConfidenceTooWide
+    );
+
+    let scale = oracle_price.expo
+        .checked_add(USDC_DECIMALS)
+        .ok_or(ErrorCode::InvalidPrice)?;
+    let factor = 10u128
+        .checked_pow(scale.unsigned_abs())
+        .ok_or(ErrorCode::InvalidPrice)?;
+    let usdc_per_sol = if scale >= 0 {
+        (oracle_price.price as u128).checked_mul(factor).ok_or(ErrorCode::Overflow)?
+    } else {
+        (oracle_price.price as u128) / factor
+    };
+
+    require!(usdc_per_sol > 0, ErrorCode::InvalidPrice);
+    u64::try_from(usdc_per_sol).map_err(|_| ErrorCode::Overflow.into())
+}
+
+fn refresh_sol_price(pool_state: &mut PoolState, price_feed: &AccountInfo) -> Result<()> {
+    pool_state.sol_price = load_sol_price(price_feed, pool_state)?;
+    pool_state.sol_price_updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+fn sol_to_usdc(lamports: u64, sol_price: u64) -> Result<u64> {
+    let value = (lamports as u128)
+        .checked_mul(sol_price as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / LAMPORTS_PER_SOL;
+
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+fn usdc_to_sol(value: u64, sol_price: u64) -> Result<u64> {
+    let lamports = (value as u128)
+        .checked_mul(LAMPORTS_PER_SOL)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(sol_price as u128)
+        .ok_or(ErrorCode::DivisionByZero)?;
+
+    u64::try_from(lamports).map_err(|_| ErrorCode::Overflow.into())
+}
+
+fn calculate_withdrawal_fee(coverage_ratio: u16) -> u16 {
+    match coverage_ratio {
+        0..=5000 => 10000,
+        5001..=7000 => 5000,
+        7001..=9000 => 2000,
+        9001..=11000 => 500,
+        11001..=13000 => 100,
+        _ => 0,
+    }
+}
+
+fn calculate_net_profit(pool_state: &PoolState) -> Result<u64> {
+    let gross_revenue = pool_state.total_premiums_collected
+        .checked_add(pool_state.total_interest_earned)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    let net_profit = gross_revenue
+        .checked_sub(pool_state.total_claims_paid)
+        .ok_or(ErrorCode::Underflow)?;
+    
+    Ok(net_profit)
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PoolState::INIT_SPACE,
+        seeds = [b"pool_state"],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = pool_state,
+        seeds = [b"pool_vault_usdc"],
+        bump
+    )]
+    pub pool_vault_usdc: Account<'info, TokenAccount>,
 
     /// CHECK: SOL vault PDA
     #[account(
@@ -709,6 +1403,9 @@ pub struct DepositLPUSDC<'info> {
     #[account(mut)]
     pub depositor_usdc: Account<'info, TokenAccount>,
 
+    /// CHECK: SOL/USD oracle price account, validated in load_sol_price; only read while the pool holds SOL
+    pub sol_price_feed: AccountInfo<'info>,
+
     #[account(
         init_if_needed,
         payer = depositor,
@@ -751,6 +1448,9 @@ pub struct DepositLPSOL<'info> {
     )]
     pub pool_vault_sol: AccountInfo<'info>,
 
+    /// CHECK: SOL/USD oracle price account, validated in load_sol_price
+    pub sol_price_feed: AccountInfo<'info>,
+
     #[account(
         mut,
         seeds = [b"lp_token_mint"],
@@ -806,6 +1506,9 @@ pub lp_token_mint: Account<'info, Mint>,
 #[account(mut)]
 pub withdrawer_usdc: Account<'info, TokenAccount>,
 
+/// CHECK: SOL/USD oracle price account, validated in load_sol_price; only read while the pool holds SOL
+pub sol_price_feed: AccountInfo<'info>,
+
 #[account(mut)]
 pub withdrawer_lp_token: Account<'info, TokenAccount>,
 
@@ -837,6 +1540,9 @@ pub lp_position: Account<'info, LPPosition>,
 )]
 pub pool_vault_sol: AccountInfo<'info>,
 
+/// CHECK: SOL/USD oracle price account, validated in load_sol_price
+pub sol_price_feed: AccountInfo<'info>,
+
 #[account(
     mut,
     seeds = [b"lp_token_mint"],
@@ -851,7 +1557,9 @@ pub withdrawer_lp_token: Account<'info, TokenAccount>,
 pub withdrawer: Signer<'info>,
 
 pub token_program: Program<'info, Token>,
-}
+
+pub system_program: Program<'info, System>,
+}
 #[derive(Accounts)]
 pub struct RecordPremium<'info> {
 #[account(
@@ -860,10 +1568,40 @@ seeds = [b"pool_state"],
 bump = pool_state.bump
 )]
 pub pool_state: Account<'info, PoolState>,
-/// CHECK: Only policy-manager program can call this
+/// CHECK: Only the policy-manager program's authority PDA can call this
+#[account(
+    constraint = policy_manager.key() == pool_state.policy_manager_authority @ ErrorCode::UnauthorizedCaller
+)]
 pub policy_manager: Signer<'info>,
 }
 #[derive(Accounts)]
+pub struct RefundPremium<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault_usdc"],
+        bump
+    )]
+    pub pool_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_usdc: Account<'info, TokenAccount>,
+
+    /// CHECK: Only the policy-manager program's authority PDA can call this
+    #[account(
+        constraint = policy_manager.key() == pool_state.policy_manager_authority @ ErrorCode::UnauthorizedCaller
+    )]
+    pub policy_manager: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+#[derive(Accounts)]
 pub struct ExecutePayout<'info> {
 #[account(
 mut,
@@ -893,10 +1631,17 @@ pub claimant_usdc: Account<'info, TokenAccount>,
 #[account(mut)]
 pub claimant: AccountInfo<'info>,
 
-/// CHECK: Only claims-processor program can call this
+/// CHECK: SOL/USD oracle price account, validated in load_sol_price; only read for SOL payouts
+pub sol_price_feed: AccountInfo<'info>,
+
+/// CHECK: Only the claims-processor program's authority PDA can call this
+#[account(
+    constraint = claims_processor.key() == pool_state.claims_processor_authority @ ErrorCode::UnauthorizedCaller
+)]
 pub claims_processor: Signer<'info>,
 
 pub token_program: Program<'info, Token>,
+pub system_program: Program<'info, System>,
 }
 #[derive(Accounts)]
 #[instruction(epoch: u64)]
@@ -922,35 +1667,223 @@ pub authority: Signer<'info>,
 pub system_program: Program<'info, System>,
 }
 #[derive(Accounts)]
-pub struct UpdateSCR<'info> {
-#[account(
-mut,
-seeds = [b"pool_state"],
-bump = pool_state.bump
-)]
-pub pool_state: Account<'info, PoolState>,
-pub authority: Signer<'info>,
+pub struct InitializeRewardVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        seeds = [b"pool_vault_usdc"],
+        bump
+    )]
+    pub pool_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = pool_state,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(address = pool_vault_usdc.mint)]
+    pub usdc_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 #[derive(Accounts)]
 pub struct DistributeRewards<'info> {
-#[account(
-seeds = [b"pool_state"],
-bump = pool_state.bump
-)]
-pub pool_state: Account<'info, PoolState>,
-#[account(
-    mut,
-    seeds = [b"lp_position", lp_position.owner.as_ref()],
-    bump = lp_position.bump
-)]
-pub lp_position: Account<'info, LPPosition>,
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
 
-pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool_vault_usdc"],
+        bump
+    )]
+    pub pool_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_position", owner.key().as_ref()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Account<'info, LPPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_usdc.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub owner_usdc: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    pub authority: Signer<'info>,
+}
+#[derive(Accounts)]
+pub struct QueueParamChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingChange::INIT_SPACE,
+        seeds = [b"param_change".as_ref(), &(pool_state.param_change_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelParameterChange<'info> {
+    #[account(
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub authority: Signer<'info>,
+}
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseState<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    pub guardian: Signer<'info>,
 }
 #[account]
 #[derive(InitSpace)]
 pub struct PoolState {
 pub authority: Pubkey,
+pub pending_authority: Option<Pubkey>,
 pub pool_vault_usdc: Pubkey,
 pub pool_vault_sol: Pubkey,
 pub lp_token_mint: Pubkey,
@@ -964,6 +1897,36 @@ pub total_interest_earned: u64,
 pub last_interest_snapshot: i64,
 pub lp_fee_bps: u16,
 pub scr_coverage_ratio: u16,
+pub policy_manager: Pubkey,
+pub policy_manager_authority: Pubkey,
+pub claims_processor: Pubkey,
+pub claims_processor_authority: Pubkey,
+pub oracle_program: Pubkey,
+pub sol_price_feed: Pubkey,
+pub max_price_age: i64,
+pub max_price_conf_bps: u16,
+pub sol_price: u64,
+pub sol_price_updated_at: i64,
+pub reward_vault: Pubkey,
+pub acc_reward_per_share: u128,
+pub total_rewards_distributed: u64,
+pub total_rewards_claimed: u64,
+pub guardian: Pubkey,
+pub paused: bool,
+pub deposits_paused: bool,
+pub withdrawals_paused: bool,
+pub timelock_delay: i64,
+pub param_change_count: u64,
+pub bump: u8,
+}
+#[account]
+#[derive(InitSpace)]
+pub struct PendingChange {
+pub id: u64,
+pub change: ParamChange,
+pub queued_at: i64,
+pub eta: i64,
+pub status: ParamChangeStatus,
 pub bump: u8,
 }
 #[account]
@@ -974,6 +1937,9 @@ pub lp_tokens: u64,
 pub usdc_deposited: u64,
 pub sol_deposited: u64,
 pub rewards_earned: u64,
+pub pending_rewards: u64,
+pub rewards_claimed: u64,
+pub reward_debt: u128,
 pub last_deposit: i64,
 pub last_withdrawal: i64,
 pub bump: u8,
@@ -988,10 +1954,20 @@ pub interest_rate_bps: u16,
 pub interest_accrued: u64,
 pub bump: u8,
 }
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
-pub enum AssetType {
-USDC,
-SOL,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ParamChange {
+SCR { new_scr: u64 },
+Oracle {
+oracle_program: Pubkey,
+sol_price_feed: Pubkey,
+max_price_age: i64,
+max_price_conf_bps: u16,
+},
+TrustedCallers {
+policy_manager: Pubkey,
+claims_processor: Pubkey,
+},
+TimelockDelay { new_delay: i64 },
 }
 #[event]
 pub struct DepositEvent {
@@ -1015,6 +1991,13 @@ pub total_premiums: u64,
 pub timestamp: i64,
 }
 #[event]
+pub struct PremiumRefundedEvent {
+pub amount: u64,
+pub recipient: Pubkey,
+pub total_premiums: u64,
+pub timestamp: i64,
+}
+#[event]
 pub struct PayoutEvent {
 pub claimant: Pubkey,
 pub amount: u64,
@@ -1035,9 +2018,88 @@ pub coverage_ratio: u16,
 pub timestamp: i64,
 }
 #[event]
+pub struct OracleConfiguredEvent {
+pub oracle_program: Pubkey,
+pub sol_price_feed: Pubkey,
+pub max_price_age: i64,
+pub max_price_conf_bps: u16,
+pub timestamp: i64,
+}
+#[event]
+pub struct TrustedCallersUpdatedEvent {
+pub policy_manager: Pubkey,
+pub policy_manager_authority: Pubkey,
+pub claims_processor: Pubkey,
+pub claims_processor_authority: Pubkey,
+pub timestamp: i64,
+}
+#[event]
 pub struct RewardDistributionEvent {
+pub amount: u64,
+pub acc_reward_per_share: u128,
+pub total_distributed: u64,
+pub timestamp: i64,
+}
+#[event]
+pub struct RewardsClaimedEvent {
 pub lp_owner: Pubkey,
-pub reward_amount: u64,
+pub amount: u64,
+pub timestamp: i64,
+}
+#[event]
+pub struct AuthorityTransferProposedEvent {
+pub current_authority: Pubkey,
+pub pending_authority: Pubkey,
+pub timestamp: i64,
+}
+#[event]
+pub struct AuthorityTransferCancelledEvent {
+pub authority: Pubkey,
+pub cancelled_authority: Pubkey,
+pub timestamp: i64,
+}
+#[event]
+pub struct AuthorityUpdatedEvent {
+pub old_authority: Pubkey,
+pub new_authority: Pubkey,
+pub timestamp: i64,
+}
+#[event]
+pub struct ParamChangeQueuedEvent {
+pub id: u64,
+pub change: ParamChange,
+pub eta: i64,
+pub timestamp: i64,
+}
+#[event]
+pub struct ParamChangeExecutedEvent {
+pub id: u64,
+pub change: ParamChange,
+pub timestamp: i64,
+}
+#[event]
+pub struct ParamChangeCancelledEvent {
+pub id: u64,
+pub timestamp: i64,
+}
+#[event]
+pub struct TimelockDelayUpdatedEvent {
+pub old_delay: i64,
+pub new_delay: i64,
+pub timestamp: i64,
+}
+#[event]
+pub struct GuardianUpdatedEvent {
+pub old_guardian: Pubkey,
+pub new_guardian: Pubkey,
+pub timestamp: i64,
+}
+#[event]
+pub struct PauseStateUpdatedEvent {
+pub guardian: Pubkey,
+pub paused: bool,
+pub deposits_paused: bool,
+pub withdrawals_paused: bool,
 pub timestamp: i64,
 }
 #[error_code]
@@ -1064,4 +2126,184 @@ DivisionByZero,
 
 #[msg("Unauthorized")]
 Unauthorized,
-}
\ No newline at end of file
+
+#[msg("Caller is not a trusted program authority")]
+UnauthorizedCaller,
+
+#[msg("Invalid oracle configuration")]
+InvalidOracleConfig,
+
+#[msg("Price feed account does not match the configured oracle")]
+InvalidPriceFeed,
+
+#[msg("Oracle price is invalid")]
+InvalidPrice,
+
+#[msg("Oracle price is stale")]
+StalePrice,
+
+#[msg("Oracle price confidence interval too wide")]
+PriceConfidenceTooWide,
+
+#[msg("No liquidity providers to distribute rewards to")]
+NoLiquidityProviders,
+
+#[msg("Amount exceeds undistributed LP profit")]
+ExceedsDistributableProfit,
+
+#[msg("No rewards to claim")]
+NoRewardsToClaim,
+
+#[msg("No authority transfer is pending")]
+NoPendingAuthority,
+
+#[msg("Timelock delay out of bounds")]
+InvalidTimelockDelay,
+
+#[msg("Parameter change is not queued")]
+ChangeNotQueued,
+
+#[msg("Timelock has not elapsed")]
+TimelockNotElapsed,
+
+#[msg("Program is paused")]
+ProgramPaused,
+
+#[msg("Deposits are paused")]
+DepositsPaused,
+
+#[msg("Withdrawals are paused")]
+WithdrawalsPaused,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL_PRICE: u64 = 150_000_000;
+
+    fn pool_state() -> PoolState {
+        PoolState {
+            authority: Pubkey::default(),
+            pending_authority: None,
+            pool_vault_usdc: Pubkey::default(),
+            pool_vault_sol: Pubkey::default(),
+            lp_token_mint: Pubkey::default(),
+            total_lp_supply: 0,
+            total_capital_usdc: 0,
+            total_capital_sol: 0,
+            statutory_capital_required: 0,
+            total_premiums_collected: 0,
+            total_claims_paid: 0,
+            total_interest_earned: 0,
+            last_interest_snapshot: 0,
+            lp_fee_bps: 0,
+            scr_coverage_ratio: 10000,
+            policy_manager: Pubkey::default(),
+            policy_manager_authority: Pubkey::default(),
+            claims_processor: Pubkey::default(),
+            claims_processor_authority: Pubkey::default(),
+            oracle_program: Pubkey::default(),
+            sol_price_feed: Pubkey::default(),
+            max_price_age: 0,
+            max_price_conf_bps: 0,
+            sol_price: SOL_PRICE,
+            sol_price_updated_at: 0,
+            reward_vault: Pubkey::default(),
+            acc_reward_per_share: 0,
+            total_rewards_distributed: 0,
+            total_rewards_claimed: 0,
+            guardian: Pubkey::default(),
+            paused: false,
+            deposits_paused: false,
+            withdrawals_paused: false,
+            timelock_delay: 0,
+            param_change_count: 0,
+            bump: 0,
+        }
+    }
+
+    fn lp_position(lp_tokens: u64) -> LPPosition {
+        LPPosition {
+            owner: Pubkey::default(),
+            lp_tokens,
+            usdc_deposited: 0,
+            sol_deposited: 0,
+            rewards_earned: 0,
+            pending_rewards: 0,
+            rewards_claimed: 0,
+            reward_debt: 0,
+            last_deposit: 0,
+            last_withdrawal: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn rewards_accrue_only_after_joining() {
+        let mut pool = pool_state();
+        pool.acc_reward_per_share = REWARD_PRECISION;
+
+        // A late depositor is checkpointed at the current accumulator
+        let mut position = lp_position(1_000);
+        checkpoint_rewards(&pool, &mut position).unwrap();
+        settle_rewards(&pool, &mut position).unwrap();
+        assert_eq!(position.pending_rewards, 0);
+
+        pool.acc_reward_per_share = REWARD_PRECISION * 7 / 2;
+        settle_rewards(&pool, &mut position).unwrap();
+        assert_eq!(position.pending_rewards, 2_500);
+        assert_eq!(position.rewards_earned, 2_500);
+
+        // Settling again after a checkpoint pays nothing twice
+        checkpoint_rewards(&pool, &mut position).unwrap();
+        settle_rewards(&pool, &mut position).unwrap();
+        assert_eq!(position.pending_rewards, 2_500);
+    }
+
+    #[test]
+    fn sol_converts_at_the_oracle_price() {
+        assert_eq!(sol_to_usdc(2_000_000_000, SOL_PRICE).unwrap(), 300_000_000);
+        assert_eq!(usdc_to_sol(300_000_000, SOL_PRICE).unwrap(), 2_000_000_000);
+        // Conversions round down, in the pool's favour
+        assert_eq!(usdc_to_sol(1, SOL_PRICE).unwrap(), 6);
+        assert_eq!(usdc_to_sol(1, 0), Err(ErrorCode::DivisionByZero.into()));
+    }
+
+    #[test]
+    fn lp_tokens_are_priced_after_a_usdc_payout() {
+        let mut pool = pool_state();
+        pool.total_capital_usdc = 100_000_000;
+        pool.total_lp_supply = 100_000_000;
+
+        assert_eq!(book_payout(&mut pool, 60_000_000, AssetType::USDC).unwrap(), 60_000_000);
+        assert_eq!(pool.total_claims_paid, 60_000_000);
+        assert_eq!(calculate_pool_value(&pool).unwrap(), 40_000_000);
+        // Each LP token is now worth 0.4 USDC
+        assert_eq!(calculate_lp_tokens_to_mint(40_000_000, &pool).unwrap(), 100_000_000);
+        assert_eq!(calculate_withdrawal_amount(50_000_000, &pool).unwrap(), (19_000_000, 1_000_000));
+    }
+
+    #[test]
+    fn lp_tokens_are_priced_after_a_sol_payout() {
+        let mut pool = pool_state();
+        pool.total_capital_usdc = 150_000_000;
+        pool.total_capital_sol = 1_000_000_000;
+        pool.total_lp_supply = 300_000_000;
+
+        // 75 USDC of value leaves as half a SOL
+        assert_eq!(book_payout(&mut pool, 75_000_000, AssetType::SOL).unwrap(), 500_000_000);
+        assert_eq!(pool.total_capital_sol, 500_000_000);
+        assert_eq!(calculate_pool_value(&pool).unwrap(), 225_000_000);
+        assert_eq!(calculate_lp_tokens_to_mint(75_000_000, &pool).unwrap(), 100_000_000);
+    }
+
+    #[test]
+    fn unpriceable_sol_adds_no_coverage() {
+        let mut pool = pool_state();
+        pool.total_capital_usdc = 50_000_000;
+        pool.total_capital_sol = u64::MAX;
+        pool.sol_price = u64::MAX;
+        pool.statutory_capital_required = 100_000_000;
+        assert_eq!(calculate_scr_coverage(&pool), 5000);
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use protocol_types::OraclePrice;
+
+declare_id!("GFBefKj1TyLSHeysohzup77GQ9FgTkZJSyLKvYnwJEjK");
+
+// Stand-in for a Pyth/Switchboard feed on localnet. PriceFeed keeps the
+// OraclePrice as its first field so consumers can read it right after the
+// account discriminator, exactly as they would a real feed adapter.
+
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    pub fn initialize_price_feed(
+        ctx: Context<InitializePriceFeed>,
+        feed_id: u64,
+        price: i64,
+        conf: u64,
+        expo: i32,
+    ) -> Result<()> {
+        let price_feed = &mut ctx.accounts.price_feed;
+        price_feed.price = OraclePrice {
+            price,
+            conf,
+            expo,
+            publish_time: Clock::get()?.unix_timestamp,
+        };
+        price_feed.authority = ctx.accounts.authority.key();
+        price_feed.feed_id = feed_id;
+        price_feed.bump = ctx.bumps.price_feed;
+
+        msg!("Price feed {} initialized: {} x 10^{}", feed_id, price, expo);
+        Ok(())
+    }
+
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: Option<i64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.price_feed.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let price_feed = &mut ctx.accounts.price_feed;
+        price_feed.price = OraclePrice {
+            price,
+            conf,
+            expo,
+            publish_time: publish_time.unwrap_or(Clock::get()?.unix_timestamp),
+        };
+
+        msg!("Price feed {} updated: {} x 10^{}", price_feed.feed_id, price, expo);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: u64)]
+pub struct InitializePriceFeed<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [b"price_feed", feed_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"price_feed", price_feed.feed_id.to_le_bytes().as_ref()],
+        bump = price_feed.bump
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeed {
+    pub price: OraclePrice,
+    pub authority: Pubkey,
+    pub feed_id: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Unauthorized")]
+    Unauthorized,
+}
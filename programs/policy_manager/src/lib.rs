@@ -1,8 +1,41 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use liquidity_pool::{program::LiquidityPool, PoolState};
+pub use protocol_types::{
+    AssetClass, AssetType, ClaimType, CoverageType, DeductibleModel, ParamChangeStatus, PaymentFrequency,
+    PolicyStatus,
+};
+use protocol_types::{DEFAULT_TIMELOCK_DELAY, MAX_TIMELOCK_DELAY, MIN_TIMELOCK_DELAY};
+use treasury::{program::Treasury, TreasuryState};
 
 declare_id!("Po1icy11111111111111111111111111111111111111");
 
+// Regulatory bounds on the protocol configuration
+const MAX_PLATFORM_FEE_BPS: u16 = 2000;
+const MAX_POLICIES_LIMIT: u64 = 5000;
+const MAX_INSURED_VALUE_LIMIT: u64 = 30_000_000_000;
+
+const PAYMENT_PERIOD: i64 = 30 * 86400;
+const DEFAULT_GRACE_PERIOD: i64 = 15 * 86400;
+const MAX_GRACE_PERIOD: i64 = 90 * 86400;
+
+const DEFAULT_CANCELLATION_FEE_BPS: u16 = 1000;
+const MAX_CANCELLATION_FEE_BPS: u16 = 2500;
+
+// Statutory withdrawal right (Widerrufsrecht) is at least 14 days
+const DEFAULT_COOLING_OFF_PERIOD: i64 = 14 * 86400;
+const MIN_COOLING_OFF_PERIOD: i64 = 14 * 86400;
+const MAX_COOLING_OFF_PERIOD: i64 = 30 * 86400;
+const DEFAULT_CLAIMS_WAITING_PERIOD: i64 = 14 * 86400;
+const MAX_CLAIMS_WAITING_PERIOD: i64 = 90 * 86400;
+
+const MAX_PRODUCT_CLAIM_TYPES: usize = 4;
+// Aggregate cover per term, as a multiple of the sum insured
+const MAX_AGGREGATE_LIMIT_BPS: u16 = 30000;
+
+// KYC checks older than this must be redone before a policy can be renewed
+const KYC_VALIDITY_PERIOD: i64 = 365 * 86400;
+
 #[program]
 pub mod policy_manager {
     use super::*;
@@ -13,12 +46,11 @@ pub mod policy_manager {
         max_policies: u64,
         max_insured_value: u64,
     ) -> Result<()> {
-        require!(platform_fee_bps <= 2000, ErrorCode::FeeTooHigh);
-        require!(max_policies <= 5000, ErrorCode::ExceedsRegulationLimit);
-        require!(max_insured_value <= 30_000_000_000, ErrorCode::ExceedsRegulationLimit);
+        validate_protocol_config(platform_fee_bps, max_policies, max_insured_value)?;
 
         let protocol_state = &mut ctx.accounts.protocol_state;
         protocol_state.authority = ctx.accounts.authority.key();
+        protocol_state.pending_authority = None;
         protocol_state.treasury = ctx.accounts.treasury.key();
         protocol_state.liquidity_pool = ctx.accounts.liquidity_pool.key();
         protocol_state.platform_fee_bps = platform_fee_bps;
@@ -27,6 +59,19 @@ pub mod policy_manager {
         protocol_state.max_policies = max_policies;
         protocol_state.max_insured_value = max_insured_value;
         protocol_state.total_premiums_collected = 0;
+        protocol_state.claims_processor = Pubkey::default();
+        protocol_state.claims_processor_authority = Pubkey::default();
+        protocol_state.guardian = ctx.accounts.authority.key();
+        protocol_state.paused = false;
+        protocol_state.policies_paused = false;
+        protocol_state.premiums_paused = false;
+        protocol_state.grace_period = DEFAULT_GRACE_PERIOD;
+        protocol_state.cancellation_fee_bps = DEFAULT_CANCELLATION_FEE_BPS;
+        protocol_state.cooling_off_period = DEFAULT_COOLING_OFF_PERIOD;
+        protocol_state.claims_waiting_period = DEFAULT_CLAIMS_WAITING_PERIOD;
+        protocol_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        protocol_state.param_change_count = 0;
+        protocol_state.product_count = 0;
         protocol_state.bump = ctx.bumps.protocol_state;
 
         emit!(ProtocolInitializedEvent {
@@ -44,6 +89,8 @@ pub mod policy_manager {
         ctx: Context<RegisterCustomer>,
         kyc_hash: [u8; 32],
     ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+
         let customer = &mut ctx.accounts.customer;
         customer.owner = ctx.accounts.owner.key();
         customer.kyc_hash = kyc_hash;
@@ -88,19 +135,28 @@ pub mod policy_manager {
 
     pub fn create_policy(
         ctx: Context<CreatePolicy>,
-        watch_details_hash: [u8; 32],
-        insured_value: u64,
-        premium: u64,
-        deductible_bps: u16,
-        coverage_type: CoverageType,
-        payment_frequency: PaymentFrequency,
-        duration_days: u32,
+        params: PolicyParams,
     ) -> Result<()> {
+        let PolicyParams {
+            asset_details_hash,
+            insured_value,
+            premium,
+            deductible_bps,
+            deductible_model,
+            payment_frequency,
+            duration_days,
+        } = params;
+
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.protocol_state.policies_paused, ErrorCode::PoliciesPaused);
+
         let protocol_state = &mut ctx.accounts.protocol_state;
         let customer = &mut ctx.accounts.customer;
+        let product = &ctx.accounts.product;
         let policy = &mut ctx.accounts.policy;
 
         require!(customer.kyc_verified, ErrorCode::KYCNotVerified);
+        require!(product.active, ErrorCode::ProductInactive);
         require!(
             protocol_state.total_policies < protocol_state.max_policies,
             ErrorCode::MaxPoliciesReached
@@ -109,27 +165,81 @@ pub mod policy_manager {
             insured_value <= protocol_state.max_insured_value,
             ErrorCode::InsuredValueTooHigh
         );
+        require!(
+            insured_value >= product.min_insured_value && insured_value <= product.max_insured_value,
+            ErrorCode::InsuredValueOutOfRange
+        );
         require!(premium > 0, ErrorCode::InvalidAmount);
-        require!(deductible_bps <= 10000, ErrorCode::InvalidDeductible);
-        require!(duration_days >= 30 && duration_days <= 365, ErrorCode::InvalidDuration);
+        require!(
+            deductible_bps >= product.min_deductible_bps && deductible_bps <= product.max_deductible_bps,
+            ErrorCode::InvalidDeductible
+        );
+        validate_deductible_model(deductible_model, insured_value)?;
+        require!(
+            duration_days >= product.min_duration_days && duration_days <= product.max_duration_days,
+            ErrorCode::InvalidDuration
+        );
+
+        let coverage_type = product.coverage_type;
+        let per_claim_limit = insured_value
+            .checked_mul(product.per_claim_limit_bps as u64)
+            .ok_or(ErrorCode::Overflow)?
+            / 10000;
+        let aggregate_limit = insured_value
+            .checked_mul(product.aggregate_limit_bps as u64)
+            .ok_or(ErrorCode::Overflow)?
+            / 10000;
+
+        let min_premium = calculate_min_premium(
+            &ctx.accounts.rate_table,
+            insured_value,
+            deductible_bps,
+            coverage_type,
+            payment_frequency,
+            duration_days,
+            customer.fraud_flags,
+        )?;
+        require!(premium >= min_premium, ErrorCode::PremiumBelowMinimum);
 
         let clock = Clock::get()?;
         let policy_id = protocol_state.total_policies + 1;
 
         policy.policy_id = policy_id;
         policy.customer = customer.owner;
+        policy.product_id = product.product_id;
         policy.coverage_type = coverage_type;
-        policy.watch_details_hash = watch_details_hash;
+        policy.asset_details_hash = asset_details_hash;
         policy.insured_value = insured_value;
         policy.premium = premium;
         policy.deductible_bps = deductible_bps;
+        policy.deductible_model = deductible_model;
         policy.payment_frequency = payment_frequency;
         policy.start_date = clock.unix_timestamp;
         policy.expiry_date = clock.unix_timestamp + (duration_days as i64 * 86400);
+        policy.term_start = clock.unix_timestamp;
         policy.status = PolicyStatus::Active;
         policy.total_paid = 0;
-        policy.next_payment_due = clock.unix_timestamp + 30 * 86400;
+        policy.term_paid = 0;
+        policy.term_fees_paid = 0;
+        // The first premium gets a grace period to arrive before the policy
+        // counts as delinquent; cover is only backdated to the start date
+        // once it is paid
+        policy.next_payment_due = clock.unix_timestamp
+            .checked_add(protocol_state.grace_period)
+            .ok_or(ErrorCode::Overflow)?;
         policy.claim_count = 0;
+        policy.per_claim_limit = per_claim_limit;
+        policy.aggregate_limit = aggregate_limit;
+        policy.remaining_aggregate = aggregate_limit;
+        policy.renewal_count = 0;
+        policy.claim_free_years = 0;
+        policy.cooling_off_ends = clock.unix_timestamp
+            .checked_add(protocol_state.cooling_off_period)
+            .ok_or(ErrorCode::Overflow)?;
+        // The protocol-wide waiting period is a floor; products may ask for longer
+        policy.claims_waiting_ends = clock.unix_timestamp
+            .checked_add(protocol_state.claims_waiting_period.max(product.claims_waiting_period))
+            .ok_or(ErrorCode::Overflow)?;
         policy.bump = ctx.bumps.policy;
 
         protocol_state.total_policies = policy_id;
@@ -141,6 +251,7 @@ pub mod policy_manager {
 
         emit!(PolicyCreatedEvent {
             policy_id,
+            product_id: product.product_id,
             customer: customer.owner,
             insured_value,
             premium,
@@ -156,18 +267,30 @@ pub mod policy_manager {
         ctx: Context<PayPremium>,
         amount: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.protocol_state.premiums_paused, ErrorCode::PremiumsPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let policy = &mut ctx.accounts.policy;
         let protocol_state = &ctx.accounts.protocol_state;
+        let clock = Clock::get()?;
 
         require!(
-            policy.status == PolicyStatus::Active,
+            policy.status == PolicyStatus::Active || policy.status == PolicyStatus::Suspended,
             ErrorCode::PolicyNotActive
         );
 
+        // Overdue policies can only be brought current within the grace window
+        if clock.unix_timestamp > policy.next_payment_due {
+            let grace_end = policy.next_payment_due
+                .checked_add(protocol_state.grace_period)
+                .ok_or(ErrorCode::Overflow)?;
+            require!(clock.unix_timestamp < grace_end, ErrorCode::GracePeriodExpired);
+        }
+
+        let (amount_due, next_payment_due) = premium_due(policy, clock.unix_timestamp)?;
         require!(
-            amount >= policy.premium,
+            amount >= amount_due,
             ErrorCode::InsufficientPayment
         );
 
@@ -181,15 +304,23 @@ pub mod policy_manager {
             .checked_sub(platform_fee)
             .ok_or(ErrorCode::Underflow)?;
 
-        let transfer_fee_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.payer_token_account.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.payer.to_account_info(),
-            },
-        );
-        token::transfer(transfer_fee_ctx, platform_fee)?;
+        // The platform fee goes through the treasury so TreasuryState.platform_fees
+        // stays in sync with the vault balance.
+        if platform_fee > 0 {
+            let collect_fee_ctx = CpiContext::new(
+                ctx.accounts.treasury_program.to_account_info(),
+                treasury::cpi::accounts::CollectPlatformFee {
+                    treasury_state: ctx.accounts.treasury_state.to_account_info(),
+                    vault_usdc: ctx.accounts.treasury_vault_usdc.to_account_info(),
+                    vault_sol: ctx.accounts.treasury_vault_sol.to_account_info(),
+                    source_token_account: ctx.accounts.payer_token_account.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            );
+            treasury::cpi::collect_platform_fee(collect_fee_ctx, platform_fee, AssetType::USDC)?;
+        }
 
         let transfer_pool_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -201,21 +332,51 @@ pub mod policy_manager {
         );
         token::transfer(transfer_pool_ctx, pool_amount)?;
 
+        let authority_seeds: [&[u8]; 2] = [b"policy_authority", &[ctx.bumps.policy_authority]];
+        let signer = &[&authority_seeds[..]];
+
+        let record_premium_ctx = CpiContext::new_with_signer(
+            ctx.accounts.liquidity_pool_program.to_account_info(),
+            liquidity_pool::cpi::accounts::RecordPremium {
+                pool_state: ctx.accounts.pool_state.to_account_info(),
+                policy_manager: ctx.accounts.policy_authority.to_account_info(),
+            },
+            signer,
+        );
+        liquidity_pool::cpi::record_premium(record_premium_ctx, pool_amount)?;
+
         policy.total_paid = policy.total_paid
             .checked_add(amount)
             .ok_or(ErrorCode::Overflow)?;
+        policy.term_paid = policy.term_paid
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        policy.term_fees_paid = policy.term_fees_paid
+            .checked_add(platform_fee)
+            .ok_or(ErrorCode::Overflow)?;
 
-        let clock = Clock::get()?;
-        policy.next_payment_due = match policy.payment_frequency {
-            PaymentFrequency::Monthly => clock.unix_timestamp + 30 * 86400,
-            PaymentFrequency::Annual => policy.expiry_date,
-        };
+        policy.next_payment_due = next_payment_due;
 
         let protocol_state = &mut ctx.accounts.protocol_state;
         protocol_state.total_premiums_collected = protocol_state.total_premiums_collected
             .checked_add(amount)
             .ok_or(ErrorCode::Overflow)?;
 
+        if policy.status == PolicyStatus::Suspended {
+            policy.status = PolicyStatus::Active;
+            protocol_state.active_policies = protocol_state.active_policies
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+
+            emit!(PolicyReinstatedEvent {
+                policy_id: policy.policy_id,
+                customer: policy.customer,
+                amount_paid: amount,
+                next_payment_due,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
         emit!(PremiumPaidEvent {
             policy_id: policy.policy_id,
             customer: policy.customer,
@@ -229,9 +390,105 @@ pub mod policy_manager {
         Ok(())
     }
 
+    /// Extends a policy by another term under the same ID, once the current
+    /// term has ended and before the grace period runs out. The customer
+    /// signs; repricing additionally needs the protocol authority.
+    pub fn renew_policy(
+        ctx: Context<RenewPolicy>,
+        duration_days: u32,
+        new_premium: Option<u64>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.protocol_state.policies_paused, ErrorCode::PoliciesPaused);
+
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let customer = &ctx.accounts.customer;
+        let policy = &mut ctx.accounts.policy;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(customer.kyc_verified, ErrorCode::KYCNotVerified);
+        require!(
+            now - customer.kyc_verified_at <= KYC_VALIDITY_PERIOD,
+            ErrorCode::KYCExpired
+        );
+        require!(customer.fraud_flags == 0, ErrorCode::CustomerFlaggedForFraud);
+        require!(
+            duration_days >= ctx.accounts.product.min_duration_days
+                && duration_days <= ctx.accounts.product.max_duration_days,
+            ErrorCode::InvalidDuration
+        );
+
+        if let Some(premium) = new_premium {
+            require!(premium > 0, ErrorCode::InvalidAmount);
+            let authority = ctx.accounts.authority
+                .as_ref()
+                .ok_or(ErrorCode::Unauthorized)?;
+            require!(
+                authority.key() == protocol_state.authority,
+                ErrorCode::Unauthorized
+            );
+
+            policy.premium = premium;
+        }
+
+        // Whether repriced or carried over, the premium must still cover the
+        // current rate table for the new term
+        let min_premium = calculate_min_premium(
+            &ctx.accounts.rate_table,
+            policy.insured_value,
+            policy.deductible_bps,
+            policy.coverage_type,
+            policy.payment_frequency,
+            duration_days,
+            customer.fraud_flags,
+        )?;
+        require!(policy.premium >= min_premium, ErrorCode::PremiumBelowMinimum);
+
+        // Renewing opens a new term with a fresh aggregate, so it has to
+        // wait for the current term to end. The new term continues from the
+        // old expiry, so cover stays continuous and premiums for the gap are
+        // owed as arrears. Past the grace period a new policy is needed instead.
+        require!(
+            policy.status == PolicyStatus::Active || policy.status == PolicyStatus::Expired,
+            ErrorCode::PolicyNotRenewable
+        );
+        require!(now >= policy.expiry_date, ErrorCode::RenewalTooEarly);
+        let renewal_deadline = policy.expiry_date
+            .checked_add(protocol_state.grace_period)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(now < renewal_deadline, ErrorCode::PolicyNotRenewable);
+
+        if policy.status == PolicyStatus::Expired {
+            policy.status = PolicyStatus::Active;
+            protocol_state.active_policies = protocol_state.active_policies
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        start_next_term(policy, now, duration_days)?;
+
+        policy.renewal_count = policy.renewal_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(PolicyRenewedEvent {
+            policy_id: policy.policy_id,
+            customer: policy.customer,
+            expiry_date: policy.expiry_date,
+            premium: policy.premium,
+            renewal_count: policy.renewal_count,
+            timestamp: now,
+        });
+
+        msg!("Policy {} renewed until {}", policy.policy_id, policy.expiry_date);
+        Ok(())
+    }
+
     pub fn cancel_policy(
         ctx: Context<CancelPolicy>,
     ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+
         let policy = &mut ctx.accounts.policy;
         let protocol_state = &mut ctx.accounts.protocol_state;
 
@@ -239,6 +496,10 @@ pub mod policy_manager {
             ctx.accounts.customer.owner == policy.customer,
             ErrorCode::Unauthorized
         );
+        require!(
+            ctx.accounts.signer.key() == policy.customer,
+            ErrorCode::Unauthorized
+        );
 
         require!(
             policy.status == PolicyStatus::Active,
@@ -249,16 +510,81 @@ pub mod policy_manager {
             ErrorCode::CannotCancelWithClaims
         );
 
+        let now = Clock::get()?.unix_timestamp;
+
+        let cooling_off = now < policy.cooling_off_ends;
+        let (pool_refund, platform_fee_refund, cancellation_fee) =
+            calculate_cancellation_refund(policy, now, protocol_state.cancellation_fee_bps)?;
+        let refund_amount = pool_refund
+            .checked_add(platform_fee_refund)
+            .ok_or(ErrorCode::Overflow)?;
+        let unearned_premium = refund_amount
+            .checked_add(cancellation_fee)
+            .ok_or(ErrorCode::Overflow)?;
+
         policy.status = PolicyStatus::Cancelled;
 
         protocol_state.active_policies = protocol_state.active_policies
             .checked_sub(1)
             .ok_or(ErrorCode::Underflow)?;
 
+        // Refunded premiums were never really collected
+        protocol_state.total_premiums_collected = protocol_state.total_premiums_collected
+            .checked_sub(refund_amount)
+            .ok_or(ErrorCode::Underflow)?;
+
+        let authority_seeds: [&[u8]; 2] = [b"policy_authority", &[ctx.bumps.policy_authority]];
+        let signer = &[&authority_seeds[..]];
+
+        // The pool only ever received the premium net of the platform fee, so
+        // each side refunds what it holds
+        if pool_refund > 0 {
+            let refund_ctx = CpiContext::new_with_signer(
+                ctx.accounts.liquidity_pool_program.to_account_info(),
+                liquidity_pool::cpi::accounts::RefundPremium {
+                    pool_state: ctx.accounts.pool_state.to_account_info(),
+                    pool_vault_usdc: ctx.accounts.pool_vault.to_account_info(),
+                    recipient_usdc: ctx.accounts.customer_token_account.to_account_info(),
+                    policy_manager: ctx.accounts.policy_authority.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                signer,
+            );
+            liquidity_pool::cpi::refund_premium(refund_ctx, pool_refund)?;
+        }
+
+        if platform_fee_refund > 0 {
+            let refund_fee_ctx = CpiContext::new_with_signer(
+                ctx.accounts.treasury_program.to_account_info(),
+                treasury::cpi::accounts::RefundPlatformFee {
+                    treasury_state: ctx.accounts.treasury_state.to_account_info(),
+                    vault_usdc: ctx.accounts.treasury_vault_usdc.to_account_info(),
+                    recipient_usdc: ctx.accounts.customer_token_account.to_account_info(),
+                    policy_manager: ctx.accounts.policy_authority.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                signer,
+            );
+            treasury::cpi::refund_platform_fee(refund_fee_ctx, platform_fee_refund)?;
+        }
+
+        if refund_amount > 0 {
+            emit!(PremiumRefundedEvent {
+                policy_id: policy.policy_id,
+                customer: policy.customer,
+                unearned_premium,
+                cancellation_fee,
+                platform_fee_refund,
+                refund_amount,
+                cooling_off,
+                timestamp: now,
+            });
+        }
+
         emit!(PolicyCancelledEvent {
             policy_id: policy.policy_id,
             customer: policy.customer,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
         });
 
         msg!("Policy {} cancelled", policy.policy_id);
@@ -300,23 +626,53 @@ pub mod policy_manager {
         Ok(())
     }
 
+    /// Records a paid claim of `amount` against the policy's limits. The
+    /// policy stays claimable until its remaining aggregate is used up, or
+    /// straight away after a total loss since the insured asset is gone.
     pub fn mark_policy_claimed(
         ctx: Context<MarkPolicyClaimed>,
+        amount: u64,
+        total_loss: bool,
     ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+
         let policy = &mut ctx.accounts.policy;
 
+        require!(amount <= policy.per_claim_limit, ErrorCode::ExceedsPerClaimLimit);
+        require!(
+            amount <= policy.remaining_aggregate,
+            ErrorCode::ExceedsRemainingAggregate
+        );
+
         policy.claim_count = policy.claim_count
             .checked_add(1)
             .ok_or(ErrorCode::Overflow)?;
+        policy.remaining_aggregate -= amount;
+        if total_loss {
+            policy.remaining_aggregate = 0;
+        }
 
-        if policy.claim_count >= 1 {
+        if policy.remaining_aggregate == 0 {
+            if policy.status == PolicyStatus::Active {
+                let protocol_state = &mut ctx.accounts.protocol_state;
+                protocol_state.active_policies = protocol_state.active_policies
+                    .checked_sub(1)
+                    .ok_or(ErrorCode::Underflow)?;
+            }
             policy.status = PolicyStatus::Claimed;
         }
 
+        let customer = &mut ctx.accounts.customer;
+        customer.total_claims = customer.total_claims
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
         emit!(PolicyClaimedEvent {
             policy_id: policy.policy_id,
             customer: policy.customer,
             claim_number: policy.claim_count,
+            amount,
+            remaining_aggregate: policy.remaining_aggregate,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -324,6 +680,103 @@ pub mod policy_manager {
         Ok(())
     }
 
+    /// Permissionless crank. Expires `policy` plus any further policies passed
+    /// as writable remaining accounts; remaining policies that aren't due yet
+    /// are skipped so one stale entry doesn't fail the whole batch.
+    pub fn expire_policy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpirePolicy<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+
+        require!(
+            expire_if_due(&mut ctx.accounts.policy, protocol_state, now)?,
+            ErrorCode::PolicyNotExpirable
+        );
+        let mut expired: u32 = 1;
+
+        // The primary policy is only written back when the instruction exits,
+        // so a duplicate of it here would still read as Active.
+        let primary = ctx.accounts.policy.key();
+
+        for account_info in ctx.remaining_accounts.iter() {
+            if account_info.key() == primary {
+                continue;
+            }
+            require!(account_info.is_writable, ErrorCode::AccountNotWritable);
+
+            let mut policy = Account::<Policy>::try_from(account_info)?;
+            if expire_if_due(&mut policy, protocol_state, now)? {
+                policy.exit(&crate::ID)?;
+                expired += 1;
+            }
+        }
+
+        msg!("Expired {} policies", expired);
+        Ok(())
+    }
+
+    /// Permissionless crank. Suspends `policy` (and any remaining-account
+    /// policies) once a premium is overdue, and lapses suspended policies
+    /// whose grace period has run out. Holders can't pay while premiums are
+    /// paused, so neither can they fall behind.
+    pub fn process_delinquency<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessDelinquency<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.protocol_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.protocol_state.premiums_paused, ErrorCode::PremiumsPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+
+        require!(
+            update_delinquency(&mut ctx.accounts.policy, protocol_state, now)?,
+            ErrorCode::PolicyNotDelinquent
+        );
+        let mut updated: u32 = 1;
+
+        let primary = ctx.accounts.policy.key();
+
+        for account_info in ctx.remaining_accounts.iter() {
+            if account_info.key() == primary {
+                continue;
+            }
+            require!(account_info.is_writable, ErrorCode::AccountNotWritable);
+
+            let mut policy = Account::<Policy>::try_from(account_info)?;
+            if update_delinquency(&mut policy, protocol_state, now)? {
+                policy.exit(&crate::ID)?;
+                updated += 1;
+            }
+        }
+
+        msg!("Updated payment status of {} policies", updated);
+        Ok(())
+    }
+
+    /// The trusted claims processor can mark policies claimed, so changing it
+    /// is timelocked.
+    pub fn set_trusted_callers(
+        ctx: Context<QueueParamChange>,
+        claims_processor: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TrustedCallers { claims_processor };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.protocol_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
     pub fn flag_customer_fraud(
         ctx: Context<FlagCustomerFraud>,
     ) -> Result<()> {
@@ -346,103 +799,1193 @@ pub mod policy_manager {
         msg!("Customer {} flagged for fraud (flags: {})", customer.owner, customer.fraud_flags);
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeProtocol<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + ProtocolState::INIT_SPACE,
-        seeds = [b"protocol_state"],
-        bump
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    pub fn create_product(
+        ctx: Context<CreateProduct>,
+        params: ProductParams,
+    ) -> Result<()> {
+        let ProductParams {
+            asset_class,
+            coverage_type,
+            allowed_claim_types,
+            min_insured_value,
+            max_insured_value,
+            min_duration_days,
+            max_duration_days,
+            min_deductible_bps,
+            max_deductible_bps,
+            per_claim_limit_bps,
+            aggregate_limit_bps,
+            claims_waiting_period,
+        } = params;
 
-    /// CHECK: Treasury address
-    pub treasury: AccountInfo<'info>,
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        require!(
+            ctx.accounts.authority.key() == protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            !allowed_claim_types.is_empty() && allowed_claim_types.len() <= MAX_PRODUCT_CLAIM_TYPES,
+            ErrorCode::InvalidProduct
+        );
+        for (i, claim_type) in allowed_claim_types.iter().enumerate() {
+            require!(
+                !allowed_claim_types[..i].contains(claim_type),
+                ErrorCode::InvalidProduct
+            );
+        }
+        require!(
+            min_insured_value > 0
+                && min_insured_value <= max_insured_value
+                && max_insured_value <= protocol_state.max_insured_value,
+            ErrorCode::InvalidProduct
+        );
+        require!(
+            min_duration_days >= 30
+                && min_duration_days <= max_duration_days
+                && max_duration_days <= 365,
+            ErrorCode::InvalidProduct
+        );
+        require!(
+            min_deductible_bps <= max_deductible_bps && max_deductible_bps <= 10000,
+            ErrorCode::InvalidProduct
+        );
+        require!(
+            per_claim_limit_bps > 0 && per_claim_limit_bps <= 10000,
+            ErrorCode::InvalidProduct
+        );
+        require!(
+            aggregate_limit_bps >= per_claim_limit_bps && aggregate_limit_bps <= MAX_AGGREGATE_LIMIT_BPS,
+            ErrorCode::InvalidProduct
+        );
+        require!(
+            (0..=MAX_CLAIMS_WAITING_PERIOD).contains(&claims_waiting_period),
+            ErrorCode::InvalidWaitingPeriod
+        );
 
-    /// CHECK: Liquidity pool program
-    pub liquidity_pool: AccountInfo<'info>,
+        let product_id = protocol_state.product_count + 1;
+        let now = Clock::get()?.unix_timestamp;
+
+        let product = &mut ctx.accounts.product;
+        product.product_id = product_id;
+        product.asset_class = asset_class;
+        product.coverage_type = coverage_type;
+        product.allowed_claim_types = allowed_claim_types;
+        product.min_insured_value = min_insured_value;
+        product.max_insured_value = max_insured_value;
+        product.min_duration_days = min_duration_days;
+        product.max_duration_days = max_duration_days;
+        product.min_deductible_bps = min_deductible_bps;
+        product.max_deductible_bps = max_deductible_bps;
+        product.per_claim_limit_bps = per_claim_limit_bps;
+        product.aggregate_limit_bps = aggregate_limit_bps;
+        product.claims_waiting_period = claims_waiting_period;
+        product.active = true;
+        product.created_at = now;
+        product.bump = ctx.bumps.product;
+
+        protocol_state.product_count = product_id;
+
+        emit!(ProductCreatedEvent {
+            product_id,
+            asset_class,
+            coverage_type,
+            allowed_claim_types: product.allowed_claim_types.clone(),
+            timestamp: now,
+        });
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        msg!("Product {} created", product_id);
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    /// Retired products stop writing new business; existing policies keep
+    /// their cover and can still claim and renew.
+    pub fn set_product_active(ctx: Context<SetProductActive>, active: bool) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
 
-#[derive(Accounts)]
-pub struct RegisterCustomer<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + Customer::INIT_SPACE,
-        seeds = [b"customer", owner.key().as_ref()],
-        bump
-    )]
-    pub customer: Account<'info, Customer>,
+        let product = &mut ctx.accounts.product;
+        product.active = active;
 
-    #[account(mut)]
-    pub owner: Signer<'info>,
+        emit!(ProductStatusUpdatedEvent {
+            product_id: product.product_id,
+            active,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    pub system_program: Program<'info, System>,
-}
+        msg!("Product {} active: {}", product.product_id, active);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct VerifyCustomer<'info> {
-    #[account(
-        seeds = [b"protocol_state"],
-        bump = protocol_state.bump
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    pub fn set_rate_table(
+        ctx: Context<SetRateTable>,
+        theft_only_rate_bps: u16,
+        theft_and_loss_rate_bps: u16,
+        deductible_discount_bps: u16,
+        max_deductible_discount_bps: u16,
+        monthly_loading_bps: u16,
+        fraud_flag_surcharge_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            theft_only_rate_bps > 0 && theft_only_rate_bps <= 10000,
+            ErrorCode::InvalidRate
+        );
+        require!(
+            theft_and_loss_rate_bps > 0 && theft_and_loss_rate_bps <= 10000,
+            ErrorCode::InvalidRate
+        );
+        require!(deductible_discount_bps <= 10000, ErrorCode::InvalidRate);
+        require!(max_deductible_discount_bps <= 10000, ErrorCode::InvalidRate);
+        require!(monthly_loading_bps <= 10000, ErrorCode::InvalidRate);
+        require!(fraud_flag_surcharge_bps <= 10000, ErrorCode::InvalidRate);
+
+        let rate_table = &mut ctx.accounts.rate_table;
+        rate_table.theft_only_rate_bps = theft_only_rate_bps;
+        rate_table.theft_and_loss_rate_bps = theft_and_loss_rate_bps;
+        rate_table.deductible_discount_bps = deductible_discount_bps;
+        rate_table.max_deductible_discount_bps = max_deductible_discount_bps;
+        rate_table.monthly_loading_bps = monthly_loading_bps;
+        rate_table.fraud_flag_surcharge_bps = fraud_flag_surcharge_bps;
+        rate_table.updated_at = Clock::get()?.unix_timestamp;
+        rate_table.bump = ctx.bumps.rate_table;
+
+        emit!(RateTableUpdatedEvent {
+            theft_only_rate_bps,
+            theft_and_loss_rate_bps,
+            deductible_discount_bps,
+            max_deductible_discount_bps,
+            monthly_loading_bps,
+            fraud_flag_surcharge_bps,
+            timestamp: rate_table.updated_at,
+        });
 
-    #[account(
-        mut,
-        seeds = [b"customer", customer.owner.as_ref()],
-        bump = customer.bump
-    )]
-    pub customer: Account<'info, Customer>,
+        msg!("Rate table updated: theft={}bps, theft+loss={}bps", theft_only_rate_bps, theft_and_loss_rate_bps);
+        Ok(())
+    }
 
-    pub authority: Signer<'info>,
-}
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
 
-#[derive(Accounts)]
-pub struct CreatePolicy<'info> {
-    #[account(
-        mut,
-        seeds = [b"protocol_state"],
-        bump = protocol_state.bump
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
+        // The new key only takes over once it signs accept_authority, so a
+        // mistyped address can be cancelled instead of locking out the admin.
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.pending_authority = Some(new_authority);
+
+        emit!(AuthorityTransferProposedEvent {
+            current_authority: protocol_state.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transfer proposed: {} -> {}", protocol_state.authority, new_authority);
+        Ok(())
+    }
+
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let new_authority = protocol_state.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
+        require!(
+            ctx.accounts.new_authority.key() == new_authority,
+            ErrorCode::Unauthorized
+        );
+
+        let old_authority = protocol_state.authority;
+        protocol_state.authority = new_authority;
+        protocol_state.pending_authority = None;
+
+        emit!(AuthorityUpdatedEvent {
+            old_authority,
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority updated: {} -> {}", old_authority, new_authority);
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(
+        ctx: Context<CancelAuthorityTransfer>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let cancelled_authority = protocol_state.pending_authority.take().ok_or(ErrorCode::NoPendingAuthority)?;
+
+        emit!(AuthorityTransferCancelledEvent {
+            authority: protocol_state.authority,
+            cancelled_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transfer to {} cancelled", cancelled_authority);
+        Ok(())
+    }
+
+    pub fn update_protocol_config(
+        ctx: Context<QueueParamChange>,
+        platform_fee_bps: u16,
+        max_policies: u64,
+        max_insured_value: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::ProtocolConfig {
+            platform_fee_bps,
+            max_policies,
+            max_insured_value,
+        };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.protocol_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn update_grace_period(
+        ctx: Context<QueueParamChange>,
+        grace_period: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::GracePeriod { grace_period };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.protocol_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn update_cancellation_fee(
+        ctx: Context<QueueParamChange>,
+        cancellation_fee_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::CancellationFee { cancellation_fee_bps };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.protocol_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn update_waiting_periods(
+        ctx: Context<QueueParamChange>,
+        cooling_off_period: i64,
+        claims_waiting_period: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::WaitingPeriods {
+            cooling_off_period,
+            claims_waiting_period,
+        };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.protocol_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn update_timelock_delay(
+        ctx: Context<QueueParamChange>,
+        new_delay: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TimelockDelay { new_delay };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.protocol_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    /// Applies a queued change once its ETA has passed. Permissionless, since
+    /// the change itself was already authorized when it was queued.
+    pub fn execute_parameter_change(
+        ctx: Context<ExecuteParameterChange>,
+    ) -> Result<()> {
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= pending_change.eta, ErrorCode::TimelockNotElapsed);
+
+        validate_param_change(&pending_change.change)?;
+        apply_param_change(&mut ctx.accounts.protocol_state, pending_change.change)?;
+        pending_change.status = ParamChangeStatus::Executed;
+
+        emit!(ParamChangeExecutedEvent {
+            id: pending_change.id,
+            change: pending_change.change,
+            timestamp: now,
+        });
+
+        msg!("Parameter change {} executed", pending_change.id);
+        Ok(())
+    }
+
+    pub fn cancel_parameter_change(
+        ctx: Context<CancelParameterChange>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        pending_change.status = ParamChangeStatus::Cancelled;
+
+        emit!(ParamChangeCancelledEvent {
+            id: pending_change.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Parameter change {} cancelled", pending_change.id);
+        Ok(())
+    }
+
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let old_guardian = protocol_state.guardian;
+        protocol_state.guardian = new_guardian;
+
+        emit!(GuardianUpdatedEvent {
+            old_guardian,
+            new_guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Guardian updated: {} -> {}", old_guardian, new_guardian);
+        Ok(())
+    }
+
+    /// Circuit breaker. Only the guardian can flip these, so a pause doesn't
+    /// have to wait on the authority key or the timelock.
+    pub fn set_pause_state(
+        ctx: Context<SetPauseState>,
+        paused: bool,
+        policies_paused: bool,
+        premiums_paused: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.guardian.key() == ctx.accounts.protocol_state.guardian,
+            ErrorCode::Unauthorized
+        );
+
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.paused = paused;
+        protocol_state.policies_paused = policies_paused;
+        protocol_state.premiums_paused = premiums_paused;
+
+        emit!(PauseStateUpdatedEvent {
+            guardian: ctx.accounts.guardian.key(),
+            paused,
+            policies_paused,
+            premiums_paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Pause state updated: paused={}, policies_paused={}, premiums_paused={}", paused, policies_paused, premiums_paused);
+        Ok(())
+    }
+}
+
+/// Moves an active or suspended policy past its expiry date to `Expired`.
+/// Returns whether it did.
+fn expire_if_due(
+    policy: &mut Policy,
+    protocol_state: &mut ProtocolState,
+    now: i64,
+) -> Result<bool> {
+    let in_force = match policy.status {
+        PolicyStatus::Active => true,
+        PolicyStatus::Suspended => false,
+        _ => return Ok(false),
+    };
+    if now < policy.expiry_date {
+        return Ok(false);
+    }
+
+    policy.status = PolicyStatus::Expired;
+    if in_force {
+        protocol_state.active_policies = protocol_state.active_policies
+            .checked_sub(1)
+            .ok_or(ErrorCode::Underflow)?;
+    }
+
+    emit!(PolicyExpiredEvent {
+        policy_id: policy.policy_id,
+        customer: policy.customer,
+        expiry_date: policy.expiry_date,
+        timestamp: now,
+    });
+
+    Ok(true)
+}
+
+/// Active -> Suspended once the premium is overdue, Suspended -> Lapsed once
+/// the grace period has passed. Returns whether the status changed.
+fn update_delinquency(
+    policy: &mut Policy,
+    protocol_state: &mut ProtocolState,
+    now: i64,
+) -> Result<bool> {
+    // Policies past expiry are handled by expire_policy
+    if now >= policy.expiry_date || now <= policy.next_payment_due {
+        return Ok(false);
+    }
+
+    let grace_end = policy.next_payment_due
+        .checked_add(protocol_state.grace_period)
+        .ok_or(ErrorCode::Overflow)?;
+
+    match policy.status {
+        PolicyStatus::Active => {
+            policy.status = PolicyStatus::Suspended;
+            protocol_state.active_policies = protocol_state.active_policies
+                .checked_sub(1)
+                .ok_or(ErrorCode::Underflow)?;
+
+            emit!(PolicySuspendedEvent {
+                policy_id: policy.policy_id,
+                customer: policy.customer,
+                payment_due: policy.next_payment_due,
+                grace_period_end: grace_end,
+                timestamp: now,
+            });
+        }
+        PolicyStatus::Suspended if now >= grace_end => {
+            policy.status = PolicyStatus::Lapsed;
+
+            emit!(PolicyLapsedEvent {
+                policy_id: policy.policy_id,
+                customer: policy.customer,
+                payment_due: policy.next_payment_due,
+                timestamp: now,
+            });
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn validate_deductible_model(model: DeductibleModel, insured_value: u64) -> Result<()> {
+    match model {
+        DeductibleModel::Percentage => {}
+        DeductibleModel::Fixed { amount } => {
+            require!(amount <= insured_value, ErrorCode::InvalidDeductible);
+        }
+        DeductibleModel::PercentageWithBounds { min, max } => {
+            require!(min <= max && max <= insured_value, ErrorCode::InvalidDeductible);
+        }
+        DeductibleModel::Disappearing { reduction_bps_per_year } => {
+            require!(reduction_bps_per_year <= 10000, ErrorCode::InvalidDeductible);
+        }
+    }
+    Ok(())
+}
+
+/// Lowest premium per payment the rate table allows. Rates are annual and
+/// applied to the insured value; annual payers cover the whole term up front,
+/// monthly payers one 30-day period plus the instalment loading.
+fn calculate_min_premium(
+    rate_table: &RateTable,
+    insured_value: u64,
+    deductible_bps: u16,
+    coverage_type: CoverageType,
+    payment_frequency: PaymentFrequency,
+    duration_days: u32,
+    fraud_flags: u8,
+) -> Result<u64> {
+    let rate_bps = match coverage_type {
+        CoverageType::TheftOnly => rate_table.theft_only_rate_bps,
+        CoverageType::TheftAndLoss => rate_table.theft_and_loss_rate_bps,
+    } as u128;
+
+    let (days, loading_bps) = match payment_frequency {
+        PaymentFrequency::Monthly => (30u128, rate_table.monthly_loading_bps as u128),
+        PaymentFrequency::Annual => (duration_days as u128, 0),
+    };
+
+    // Discount scales with the deductible, up to the table's cap
+    let discount_bps = (deductible_bps as u128 * rate_table.deductible_discount_bps as u128 / 10000)
+        .min(rate_table.max_deductible_discount_bps as u128);
+    let surcharge_bps = fraud_flags as u128 * rate_table.fraud_flag_surcharge_bps as u128;
+
+    let premium = (insured_value as u128)
+        .checked_mul(rate_bps)
+        .and_then(|v| v.checked_mul(days))
+        .and_then(|v| v.checked_mul(10000 - discount_bps))
+        .and_then(|v| v.checked_mul(10000 + loading_bps))
+        .and_then(|v| v.checked_mul(10000 + surcharge_bps))
+        .ok_or(ErrorCode::Overflow)?
+        / (10000u128 * 365 * 10000 * 10000 * 10000);
+
+    u64::try_from(premium).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+/// Rolls `policy` into its next term of `duration_days`. A term with nothing
+/// paid out counts towards a disappearing deductible; each new term then
+/// comes with a fresh aggregate.
+fn start_next_term(policy: &mut Policy, now: i64, duration_days: u32) -> Result<()> {
+    require!(now >= policy.expiry_date, ErrorCode::RenewalTooEarly);
+
+    if policy.remaining_aggregate == policy.aggregate_limit {
+        policy.claim_free_years = policy.claim_free_years.saturating_add(1);
+    } else {
+        policy.claim_free_years = 0;
+    }
+    policy.remaining_aggregate = policy.aggregate_limit;
+    policy.term_start = policy.expiry_date;
+    policy.term_paid = 0;
+    policy.term_fees_paid = 0;
+    policy.expiry_date = policy.expiry_date
+        .checked_add(duration_days as i64 * 86400)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
+
+/// Splits a cancellation refund into `(pool_refund, platform_fee_refund,
+/// cancellation_fee)`. Inside the cooling-off window everything paid comes
+/// back fee-free, including the platform fee held by the treasury.
+fn calculate_cancellation_refund(
+    policy: &Policy,
+    now: i64,
+    cancellation_fee_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    if now < policy.cooling_off_ends {
+        let pool_paid = policy.term_paid
+            .checked_sub(policy.term_fees_paid)
+            .ok_or(ErrorCode::Underflow)?;
+        return Ok((pool_paid, policy.term_fees_paid, 0));
+    }
+
+    let unearned_premium = calculate_unearned_premium(policy, now)?;
+    let cancellation_fee = unearned_premium
+        .checked_mul(cancellation_fee_bps as u64)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::DivisionByZero)?;
+    let pool_refund = unearned_premium
+        .checked_sub(cancellation_fee)
+        .ok_or(ErrorCode::Underflow)?;
+
+    Ok((pool_refund, 0, cancellation_fee))
+}
+
+/// Part of the pool's share of this term's premiums that covers time after
+/// `now`. Premiums are paid up to `next_payment_due`, so that is where paid
+/// cover ends. The platform fee never reached the pool, so it is left out.
+fn calculate_unearned_premium(policy: &Policy, now: i64) -> Result<u64> {
+    let paid_through = policy.next_payment_due.min(policy.expiry_date);
+    if now >= paid_through || paid_through <= policy.term_start {
+        return Ok(0);
+    }
+
+    let remaining = (paid_through - now.max(policy.term_start)) as u128;
+    let covered = (paid_through - policy.term_start) as u128;
+
+    let pool_paid = policy.term_paid
+        .checked_sub(policy.term_fees_paid)
+        .ok_or(ErrorCode::Underflow)?;
+
+    let unearned = (pool_paid as u128)
+        .checked_mul(remaining)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(covered)
+        .ok_or(ErrorCode::DivisionByZero)?;
+
+    u64::try_from(unearned).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+/// Premium needed to bring a policy current, and the due date it moves to
+/// once paid. Overdue monthly policies owe every missed period as arrears.
+fn premium_due(policy: &Policy, now: i64) -> Result<(u64, i64)> {
+    // Until the first premium arrives nothing is paid beyond the start date
+    let paid_through = if policy.total_paid == 0 {
+        policy.start_date
+    } else {
+        policy.next_payment_due
+    };
+
+    match policy.payment_frequency {
+        PaymentFrequency::Monthly => {
+            let periods = if now > paid_through {
+                (now - paid_through) / PAYMENT_PERIOD + 1
+            } else {
+                1
+            };
+            let amount = policy.premium
+                .checked_mul(periods as u64)
+                .ok_or(ErrorCode::Overflow)?;
+            let next_payment_due = periods
+                .checked_mul(PAYMENT_PERIOD)
+                .and_then(|extension| paid_through.checked_add(extension))
+                .ok_or(ErrorCode::Overflow)?;
+            Ok((amount, next_payment_due))
+        }
+        PaymentFrequency::Annual => Ok((policy.premium, policy.expiry_date)),
+    }
+}
+
+/// Stores `change` as the next pending change, executable after the current timelock delay
+fn queue_param_change(
+    protocol_state: &mut ProtocolState,
+    pending_change: &mut PendingChange,
+    change: ParamChange,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    protocol_state.param_change_count = protocol_state.param_change_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    pending_change.id = protocol_state.param_change_count;
+    pending_change.change = change;
+    pending_change.queued_at = now;
+    pending_change.eta = now
+        .checked_add(protocol_state.timelock_delay)
+        .ok_or(ErrorCode::Overflow)?;
+    pending_change.status = ParamChangeStatus::Queued;
+    pending_change.bump = bump;
+
+    emit!(ParamChangeQueuedEvent {
+        id: pending_change.id,
+        change,
+        eta: pending_change.eta,
+        timestamp: now,
+    });
+
+    msg!("Parameter change {} queued: {:?}, executable at {}", pending_change.id, change, pending_change.eta);
+    Ok(())
+}
+
+/// Bounds checks shared by queueing and execution
+fn validate_param_change(change: &ParamChange) -> Result<()> {
+    match change {
+        ParamChange::TimelockDelay { new_delay } => {
+            require!(
+                (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(new_delay),
+                ErrorCode::InvalidTimelockDelay
+            );
+        }
+        ParamChange::ProtocolConfig { platform_fee_bps, max_policies, max_insured_value } => {
+            validate_protocol_config(*platform_fee_bps, *max_policies, *max_insured_value)?;
+        }
+        ParamChange::GracePeriod { grace_period } => {
+            require!(
+                (0..=MAX_GRACE_PERIOD).contains(grace_period),
+                ErrorCode::InvalidGracePeriod
+            );
+        }
+        ParamChange::CancellationFee { cancellation_fee_bps } => {
+            require!(*cancellation_fee_bps <= MAX_CANCELLATION_FEE_BPS, ErrorCode::FeeTooHigh);
+        }
+        ParamChange::WaitingPeriods { cooling_off_period, claims_waiting_period } => {
+            require!(
+                (MIN_COOLING_OFF_PERIOD..=MAX_COOLING_OFF_PERIOD).contains(cooling_off_period),
+                ErrorCode::InvalidWaitingPeriod
+            );
+            require!(
+                (0..=MAX_CLAIMS_WAITING_PERIOD).contains(claims_waiting_period),
+                ErrorCode::InvalidWaitingPeriod
+            );
+        }
+        ParamChange::TrustedCallers { .. } => {}
+    }
+    Ok(())
+}
+
+fn validate_protocol_config(
+    platform_fee_bps: u16,
+    max_policies: u64,
+    max_insured_value: u64,
+) -> Result<()> {
+    require!(platform_fee_bps <= MAX_PLATFORM_FEE_BPS, ErrorCode::FeeTooHigh);
+    require!(max_policies <= MAX_POLICIES_LIMIT, ErrorCode::ExceedsRegulationLimit);
+    require!(max_insured_value <= MAX_INSURED_VALUE_LIMIT, ErrorCode::ExceedsRegulationLimit);
+    Ok(())
+}
+
+fn apply_param_change(protocol_state: &mut ProtocolState, change: ParamChange) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    match change {
+        ParamChange::ProtocolConfig { platform_fee_bps, max_policies, max_insured_value } => {
+            let old_platform_fee_bps = protocol_state.platform_fee_bps;
+            let old_max_policies = protocol_state.max_policies;
+            let old_max_insured_value = protocol_state.max_insured_value;
+
+            protocol_state.platform_fee_bps = platform_fee_bps;
+            protocol_state.max_policies = max_policies;
+            protocol_state.max_insured_value = max_insured_value;
+
+            emit!(ProtocolConfigUpdatedEvent {
+                old_platform_fee_bps,
+                new_platform_fee_bps: platform_fee_bps,
+                old_max_policies,
+                new_max_policies: max_policies,
+                old_max_insured_value,
+                new_max_insured_value: max_insured_value,
+                timestamp,
+            });
+
+            msg!("Protocol config updated: fee={}bps, max_policies={}, max_insured_value={}", platform_fee_bps, max_policies, max_insured_value);
+        }
+        ParamChange::GracePeriod { grace_period } => {
+            let old_grace_period = protocol_state.grace_period;
+            protocol_state.grace_period = grace_period;
+
+            emit!(GracePeriodUpdatedEvent {
+                old_grace_period,
+                new_grace_period: grace_period,
+                timestamp,
+            });
+
+            msg!("Grace period updated: {}s -> {}s", old_grace_period, grace_period);
+        }
+        ParamChange::CancellationFee { cancellation_fee_bps } => {
+            let old_fee_bps = protocol_state.cancellation_fee_bps;
+            protocol_state.cancellation_fee_bps = cancellation_fee_bps;
+
+            emit!(CancellationFeeUpdatedEvent {
+                old_fee_bps,
+                new_fee_bps: cancellation_fee_bps,
+                timestamp,
+            });
+
+            msg!("Cancellation fee updated: {}bps -> {}bps", old_fee_bps, cancellation_fee_bps);
+        }
+        ParamChange::WaitingPeriods { cooling_off_period, claims_waiting_period } => {
+            protocol_state.cooling_off_period = cooling_off_period;
+            protocol_state.claims_waiting_period = claims_waiting_period;
+
+            emit!(WaitingPeriodsUpdatedEvent {
+                cooling_off_period,
+                claims_waiting_period,
+                timestamp,
+            });
+
+            msg!("Waiting periods updated: cooling_off={}s, claims_waiting={}s", cooling_off_period, claims_waiting_period);
+        }
+        ParamChange::TrustedCallers { claims_processor } => {
+            // The claims processor signs its CPIs with a PDA derived from its
+            // program ID; that PDA is the only accepted caller.
+            protocol_state.claims_processor = claims_processor;
+            protocol_state.claims_processor_authority =
+                Pubkey::find_program_address(&[b"claims_authority"], &claims_processor).0;
+
+            emit!(TrustedCallersUpdatedEvent {
+                claims_processor,
+                claims_processor_authority: protocol_state.claims_processor_authority,
+                timestamp,
+            });
+
+            msg!("Trusted callers updated: claims_processor={}", claims_processor);
+        }
+        ParamChange::TimelockDelay { new_delay } => {
+            let old_delay = protocol_state.timelock_delay;
+            protocol_state.timelock_delay = new_delay;
+
+            emit!(TimelockDelayUpdatedEvent {
+                old_delay,
+                new_delay,
+                timestamp,
+            });
+
+            msg!("Timelock delay updated: {}s -> {}s", old_delay, new_delay);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolState::INIT_SPACE,
+        seeds = [b"protocol_state"],
+        bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    /// CHECK: Treasury address
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Liquidity pool program
+    pub liquidity_pool: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterCustomer<'info> {
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Customer::INIT_SPACE,
+        seeds = [b"customer", owner.key().as_ref()],
+        bump
+    )]
+    pub customer: Account<'info, Customer>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCustomer<'info> {
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [b"customer", customer.owner.as_ref()],
+        bump = customer.bump
+    )]
+    pub customer: Account<'info, Customer>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [b"customer", customer.owner.as_ref()],
+        bump = customer.bump
+    )]
+    pub customer: Account<'info, Customer>,
+
+    #[account(
+        seeds = [b"product".as_ref(), &product.product_id.to_le_bytes()],
+        bump = product.bump
+    )]
+    pub product: Account<'info, Product>,
+
+    #[account(
+        seeds = [b"rate_table"],
+        bump = rate_table.bump
+    )]
+    pub rate_table: Account<'info, RateTable>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Policy::INIT_SPACE,
+        seeds = [
+            b"policy",
+            customer.owner.as_ref(),
+            &(protocol_state.total_policies + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayPremium<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"policy",
+            policy.customer.as_ref(),
+            &policy.policy_id.to_le_bytes()
+        ],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(mut)]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        seeds::program = treasury_program.key(),
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        address = treasury_state.vault_usdc @ ErrorCode::InvalidVault
+    )]
+    pub treasury_vault_usdc: Account<'info, TokenAccount>,
+
+    /// CHECK: Treasury SOL vault, only forwarded to the treasury program
+    #[account(
+        mut,
+        address = treasury_state.vault_sol @ ErrorCode::InvalidVault
+    )]
+    pub treasury_vault_sol: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        seeds::program = liquidity_pool_program.key(),
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        address = pool_state.pool_vault_usdc @ ErrorCode::InvalidVault
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA that signs cross-program calls on behalf of the policy manager
+    #[account(
+        seeds = [b"policy_authority"],
+        bump
+    )]
+    pub policy_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = treasury_program.key() == protocol_state.treasury @ ErrorCode::InvalidProgram
+    )]
+    pub treasury_program: Program<'info, Treasury>,
+
+    #[account(
+        constraint = liquidity_pool_program.key() == protocol_state.liquidity_pool @ ErrorCode::InvalidProgram
+    )]
+    pub liquidity_pool_program: Program<'info, LiquidityPool>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [b"customer", owner.key().as_ref()],
+        bump = customer.bump
+    )]
+    pub customer: Account<'info, Customer>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"policy",
+            customer.owner.as_ref(),
+            &policy.policy_id.to_le_bytes()
+        ],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        seeds = [b"product".as_ref(), &policy.product_id.to_le_bytes()],
+        bump = product.bump
+    )]
+    pub product: Account<'info, Product>,
+
+    pub owner: Signer<'info>,
 
+    #[account(
+        seeds = [b"rate_table"],
+        bump = rate_table.bump
+    )]
+    pub rate_table: Account<'info, RateTable>,
+
+    /// Required only when repricing
+    pub authority: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelPolicy<'info> {
     #[account(
         mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
         seeds = [b"customer", customer.owner.as_ref()],
         bump = customer.bump
     )]
     pub customer: Account<'info, Customer>,
 
     #[account(
-        init,
-        payer = payer,
-        space = 8 + Policy::INIT_SPACE,
+        mut,
         seeds = [
             b"policy",
-            customer.owner.as_ref(),
-            &(protocol_state.total_policies + 1).to_le_bytes()
+            policy.customer.as_ref(),
+            &policy.policy_id.to_le_bytes()
         ],
-        bump
+        bump = policy.bump
     )]
     pub policy: Account<'info, Policy>,
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool_state"],
+        seeds::program = liquidity_pool_program.key(),
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
 
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        address = pool_state.pool_vault_usdc @ ErrorCode::InvalidVault
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == policy.customer @ ErrorCode::Unauthorized
+    )]
+    pub customer_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA that signs cross-program calls on behalf of the policy manager
+    #[account(
+        seeds = [b"policy_authority"],
+        bump
+    )]
+    pub policy_authority: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = liquidity_pool_program.key() == protocol_state.liquidity_pool @ ErrorCode::InvalidProgram
+    )]
+    pub liquidity_pool_program: Program<'info, LiquidityPool>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        seeds::program = treasury_program.key(),
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        address = treasury_state.vault_usdc @ ErrorCode::InvalidVault
+    )]
+    pub treasury_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = treasury_program.key() == protocol_state.treasury @ ErrorCode::InvalidProgram
+    )]
+    pub treasury_program: Program<'info, Treasury>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct PayPremium<'info> {
+pub struct UpdatePolicyStatus<'info> {
     #[account(
         mut,
         seeds = [b"protocol_state"],
@@ -461,23 +2004,45 @@ pub struct PayPremium<'info> {
     )]
     pub policy: Account<'info, Policy>,
 
-    #[account(mut)]
-    pub payer_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
 
-    #[account(mut)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+#[derive(Accounts)]
+pub struct MarkPolicyClaimed<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
 
-    #[account(mut)]
-    pub pool_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"policy",
+            policy.customer.as_ref(),
+            &policy.policy_id.to_le_bytes()
+        ],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"customer", policy.customer.as_ref()],
+        bump = customer.bump
+    )]
+    pub customer: Account<'info, Customer>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Only the claims-processor program's authority PDA can call this
+    #[account(
+        constraint = claims_processor.key() == protocol_state.claims_processor_authority @ ErrorCode::UnauthorizedCaller
+    )]
+    pub claims_processor: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct CancelPolicy<'info> {
+pub struct ExpirePolicy<'info> {
     #[account(
         mut,
         seeds = [b"protocol_state"],
@@ -486,10 +2051,27 @@ pub struct CancelPolicy<'info> {
     pub protocol_state: Account<'info, ProtocolState>,
 
     #[account(
-        seeds = [b"customer", customer.owner.as_ref()],
-        bump = customer.bump
+        mut,
+        seeds = [
+            b"policy",
+            policy.customer.as_ref(),
+            &policy.policy_id.to_le_bytes()
+        ],
+        bump = policy.bump
     )]
-    pub customer: Account<'info, Customer>,
+    pub policy: Account<'info, Policy>,
+
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessDelinquency<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
 
     #[account(
         mut,
@@ -502,13 +2084,174 @@ pub struct CancelPolicy<'info> {
     )]
     pub policy: Account<'info, Policy>,
 
-    pub signer: Signer<'info>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FlagCustomerFraud<'info> {
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [b"customer", customer.owner.as_ref()],
+        bump = customer.bump
+    )]
+    pub customer: Account<'info, Customer>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProduct<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Product::INIT_SPACE,
+        seeds = [b"product".as_ref(), &(protocol_state.product_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub product: Account<'info, Product>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProductActive<'info> {
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [b"product".as_ref(), &product.product_id.to_le_bytes()],
+        bump = product.bump
+    )]
+    pub product: Account<'info, Product>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRateTable<'info> {
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RateTable::INIT_SPACE,
+        seeds = [b"rate_table"],
+        bump
+    )]
+    pub rate_table: Account<'info, RateTable>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueParamChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingChange::INIT_SPACE,
+        seeds = [b"param_change".as_ref(), &(protocol_state.param_change_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub executor: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct UpdatePolicyStatus<'info> {
+pub struct CancelParameterChange<'info> {
     #[account(
-        mut,
         seeds = [b"protocol_state"],
         bump = protocol_state.bump
     )]
@@ -516,57 +2259,43 @@ pub struct UpdatePolicyStatus<'info> {
 
     #[account(
         mut,
-        seeds = [
-            b"policy",
-            policy.customer.as_ref(),
-            &policy.policy_id.to_le_bytes()
-        ],
-        bump = policy.bump
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
     )]
-    pub policy: Account<'info, Policy>,
+    pub pending_change: Account<'info, PendingChange>,
 
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct MarkPolicyClaimed<'info> {
+pub struct SetGuardian<'info> {
     #[account(
         mut,
-        seeds = [
-            b"policy",
-            policy.customer.as_ref(),
-            &policy.policy_id.to_le_bytes()
-        ],
-        bump = policy.bump
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
     )]
-    pub policy: Account<'info, Policy>,
+    pub protocol_state: Account<'info, ProtocolState>,
 
-    /// CHECK: Only claims-processor program can call this
-    pub claims_processor: Signer<'info>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct FlagCustomerFraud<'info> {
+pub struct SetPauseState<'info> {
     #[account(
+        mut,
         seeds = [b"protocol_state"],
         bump = protocol_state.bump
     )]
     pub protocol_state: Account<'info, ProtocolState>,
 
-    #[account(
-        mut,
-        seeds = [b"customer", customer.owner.as_ref()],
-        bump = customer.bump
-    )]
-    pub customer: Account<'info, Customer>,
-
-    pub authority: Signer<'info>,
+    pub guardian: Signer<'info>,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct ProtocolState {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
     pub treasury: Pubkey,
     pub liquidity_pool: Pubkey,
     pub platform_fee_bps: u16,
@@ -575,6 +2304,94 @@ pub struct ProtocolState {
     pub max_policies: u64,
     pub max_insured_value: u64,
     pub total_premiums_collected: u64,
+    pub claims_processor: Pubkey,
+    pub claims_processor_authority: Pubkey,
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub policies_paused: bool,
+    pub premiums_paused: bool,
+    pub grace_period: i64,
+    pub cancellation_fee_bps: u16,
+    pub cooling_off_period: i64,
+    pub claims_waiting_period: i64,
+    pub timelock_delay: i64,
+    pub param_change_count: u64,
+    pub product_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingChange {
+    pub id: u64,
+    pub change: ParamChange,
+    pub queued_at: i64,
+    pub eta: i64,
+    pub status: ParamChangeStatus,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Product {
+    pub product_id: u64,
+    pub asset_class: AssetClass,
+    pub coverage_type: CoverageType,
+    #[max_len(4)]
+    pub allowed_claim_types: Vec<ClaimType>,
+    pub min_insured_value: u64,
+    pub max_insured_value: u64,
+    pub min_duration_days: u32,
+    pub max_duration_days: u32,
+    pub min_deductible_bps: u16,
+    pub max_deductible_bps: u16,
+    pub per_claim_limit_bps: u16,
+    pub aggregate_limit_bps: u16,
+    pub claims_waiting_period: i64,
+    pub active: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Terms a customer requests for a new policy under a product.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PolicyParams {
+    pub asset_details_hash: [u8; 32],
+    pub insured_value: u64,
+    pub premium: u64,
+    pub deductible_bps: u16,
+    pub deductible_model: DeductibleModel,
+    pub payment_frequency: PaymentFrequency,
+    pub duration_days: u32,
+}
+
+/// Terms for a new product; see `Product` for what each field governs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProductParams {
+    pub asset_class: AssetClass,
+    pub coverage_type: CoverageType,
+    pub allowed_claim_types: Vec<ClaimType>,
+    pub min_insured_value: u64,
+    pub max_insured_value: u64,
+    pub min_duration_days: u32,
+    pub max_duration_days: u32,
+    pub min_deductible_bps: u16,
+    pub max_deductible_bps: u16,
+    pub per_claim_limit_bps: u16,
+    pub aggregate_limit_bps: u16,
+    pub claims_waiting_period: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RateTable {
+    pub theft_only_rate_bps: u16,
+    pub theft_and_loss_rate_bps: u16,
+    pub deductible_discount_bps: u16,
+    pub max_deductible_discount_bps: u16,
+    pub monthly_loading_bps: u16,
+    pub fraud_flag_surcharge_bps: u16,
+    pub updated_at: i64,
     pub bump: u8,
 }
 
@@ -597,41 +2414,49 @@ pub struct Customer {
 #[derive(InitSpace)]
 pub struct Policy {
     pub policy_id: u64,
+    pub product_id: u64,
     pub customer: Pubkey,
     pub coverage_type: CoverageType,
-    pub watch_details_hash: [u8; 32],
+    pub asset_details_hash: [u8; 32],
     pub insured_value: u64,
     pub premium: u64,
     pub deductible_bps: u16,
+    pub deductible_model: DeductibleModel,
     pub payment_frequency: PaymentFrequency,
     pub start_date: i64,
     pub expiry_date: i64,
+    pub term_start: i64,
     pub status: PolicyStatus,
     pub total_paid: u64,
+    pub term_paid: u64,
+    pub term_fees_paid: u64,
     pub next_payment_due: i64,
     pub claim_count: u8,
+    pub per_claim_limit: u64,
+    pub aggregate_limit: u64,
+    pub remaining_aggregate: u64,
+    pub renewal_count: u16,
+    pub claim_free_years: u8,
+    pub cooling_off_ends: i64,
+    pub claims_waiting_ends: i64,
     pub bump: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum CoverageType {
-    TheftOnly,
-    TheftAndLoss,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum PaymentFrequency {
-    Monthly,
-    Annual,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
-pub enum PolicyStatus {
-    Active,
-    Expired,
-    Claimed,
-    Cancelled,
-    Suspended,
+pub enum ParamChange {
+    ProtocolConfig {
+        platform_fee_bps: u16,
+        max_policies: u64,
+        max_insured_value: u64,
+    },
+    GracePeriod { grace_period: i64 },
+    CancellationFee { cancellation_fee_bps: u16 },
+    WaitingPeriods {
+        cooling_off_period: i64,
+        claims_waiting_period: i64,
+    },
+    TrustedCallers { claims_processor: Pubkey },
+    TimelockDelay { new_delay: i64 },
 }
 
 #[event]
@@ -658,6 +2483,7 @@ pub struct CustomerVerifiedEvent {
 #[event]
 pub struct PolicyCreatedEvent {
     pub policy_id: u64,
+    pub product_id: u64,
     pub customer: Pubkey,
     pub insured_value: u64,
     pub premium: u64,
@@ -675,6 +2501,32 @@ pub struct PremiumPaidEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PremiumRefundedEvent {
+    pub policy_id: u64,
+    pub customer: Pubkey,
+    pub unearned_premium: u64,
+    pub cancellation_fee: u64,
+    pub platform_fee_refund: u64,
+    pub refund_amount: u64,
+    pub cooling_off: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CancellationFeeUpdatedEvent {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WaitingPeriodsUpdatedEvent {
+    pub cooling_off_period: i64,
+    pub claims_waiting_period: i64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PolicyCancelledEvent {
     pub policy_id: u64,
@@ -690,11 +2542,98 @@ pub struct PolicyStatusUpdatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PolicyExpiredEvent {
+    pub policy_id: u64,
+    pub customer: Pubkey,
+    pub expiry_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyRenewedEvent {
+    pub policy_id: u64,
+    pub customer: Pubkey,
+    pub expiry_date: i64,
+    pub premium: u64,
+    pub renewal_count: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicySuspendedEvent {
+    pub policy_id: u64,
+    pub customer: Pubkey,
+    pub payment_due: i64,
+    pub grace_period_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyLapsedEvent {
+    pub policy_id: u64,
+    pub customer: Pubkey,
+    pub payment_due: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyReinstatedEvent {
+    pub policy_id: u64,
+    pub customer: Pubkey,
+    pub amount_paid: u64,
+    pub next_payment_due: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GracePeriodUpdatedEvent {
+    pub old_grace_period: i64,
+    pub new_grace_period: i64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PolicyClaimedEvent {
     pub policy_id: u64,
     pub customer: Pubkey,
     pub claim_number: u8,
+    pub amount: u64,
+    pub remaining_aggregate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrustedCallersUpdatedEvent {
+    pub claims_processor: Pubkey,
+    pub claims_processor_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProductCreatedEvent {
+    pub product_id: u64,
+    pub asset_class: AssetClass,
+    pub coverage_type: CoverageType,
+    pub allowed_claim_types: Vec<ClaimType>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProductStatusUpdatedEvent {
+    pub product_id: u64,
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateTableUpdatedEvent {
+    pub theft_only_rate_bps: u16,
+    pub theft_and_loss_rate_bps: u16,
+    pub deductible_discount_bps: u16,
+    pub max_deductible_discount_bps: u16,
+    pub monthly_loading_bps: u16,
+    pub fraud_flag_surcharge_bps: u16,
     pub timestamp: i64,
 }
 
@@ -705,6 +2644,82 @@ pub struct CustomerFraudFlaggedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AuthorityTransferProposedEvent {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelledEvent {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityUpdatedEvent {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeQueuedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeExecutedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeCancelledEvent {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdatedEvent {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolConfigUpdatedEvent {
+    pub old_platform_fee_bps: u16,
+    pub new_platform_fee_bps: u16,
+    pub old_max_policies: u64,
+    pub new_max_policies: u64,
+    pub old_max_insured_value: u64,
+    pub new_max_insured_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseStateUpdatedEvent {
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub policies_paused: bool,
+    pub premiums_paused: bool,
+    pub timestamp: i64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Fee cannot exceed 20%")]
@@ -751,4 +2766,307 @@ pub enum ErrorCode {
 
     #[msg("Unauthorized")]
     Unauthorized,
-}
\ No newline at end of file
+
+    #[msg("Caller is not a trusted program authority")]
+    UnauthorizedCaller,
+
+    #[msg("Vault does not belong to the protocol")]
+    InvalidVault,
+
+    #[msg("Invalid program account")]
+    InvalidProgram,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Timelock delay out of bounds")]
+    InvalidTimelockDelay,
+
+    #[msg("Parameter change is not queued")]
+    ChangeNotQueued,
+
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Policy issuance is paused")]
+    PoliciesPaused,
+
+    #[msg("Premium payments are paused")]
+    PremiumsPaused,
+
+    #[msg("Policy is not active or not yet past expiry")]
+    PolicyNotExpirable,
+
+    #[msg("Account must be writable")]
+    AccountNotWritable,
+
+    #[msg("Policy premium is not overdue")]
+    PolicyNotDelinquent,
+
+    #[msg("Grace period has expired; the policy has lapsed")]
+    GracePeriodExpired,
+
+    #[msg("Grace period out of bounds")]
+    InvalidGracePeriod,
+
+    #[msg("Only active or expired policies can be renewed")]
+    PolicyNotRenewable,
+
+    #[msg("KYC verification has expired")]
+    KYCExpired,
+
+    #[msg("Customer has outstanding fraud flags")]
+    CustomerFlaggedForFraud,
+
+    #[msg("Waiting period out of bounds")]
+    InvalidWaitingPeriod,
+
+    #[msg("Invalid rate")]
+    InvalidRate,
+
+    #[msg("Premium is below the rated minimum")]
+    PremiumBelowMinimum,
+
+    #[msg("Invalid product configuration")]
+    InvalidProduct,
+
+    #[msg("Product is not accepting new policies")]
+    ProductInactive,
+
+    #[msg("Insured value outside the product's range")]
+    InsuredValueOutOfRange,
+
+    #[msg("Claim exceeds the per-claim limit")]
+    ExceedsPerClaimLimit,
+
+    #[msg("Claim exceeds the remaining aggregate limit")]
+    ExceedsRemainingAggregate,
+
+    #[msg("Policy can only be renewed once its current term has ended")]
+    RenewalTooEarly,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn annual_policy(start_date: i64, premium: u64, term_fees_paid: u64) -> Policy {
+        Policy {
+            policy_id: 1,
+            product_id: 1,
+            customer: Pubkey::default(),
+            coverage_type: CoverageType::TheftOnly,
+            asset_details_hash: [0; 32],
+            insured_value: 100_000,
+            premium,
+            deductible_bps: 1_000,
+            deductible_model: DeductibleModel::Percentage,
+            payment_frequency: PaymentFrequency::Annual,
+            start_date,
+            expiry_date: start_date + 365 * DAY,
+            term_start: start_date,
+            status: PolicyStatus::Active,
+            total_paid: premium,
+            term_paid: premium,
+            term_fees_paid,
+            next_payment_due: start_date + 365 * DAY,
+            claim_count: 0,
+            per_claim_limit: 100_000,
+            aggregate_limit: 100_000,
+            remaining_aggregate: 100_000,
+            renewal_count: 0,
+            claim_free_years: 0,
+            cooling_off_ends: start_date + DEFAULT_COOLING_OFF_PERIOD,
+            claims_waiting_ends: start_date,
+            bump: 0,
+        }
+    }
+
+    fn monthly_policy(premium: u64) -> Policy {
+        let mut policy = annual_policy(0, premium, 0);
+        policy.payment_frequency = PaymentFrequency::Monthly;
+        policy.total_paid = 0;
+        policy.term_paid = 0;
+        policy.next_payment_due = DEFAULT_GRACE_PERIOD;
+        policy
+    }
+
+    #[test]
+    fn first_premium_pays_from_the_start_date() {
+        let policy = monthly_policy(100);
+        assert_eq!(premium_due(&policy, 10 * DAY).unwrap(), (100, PAYMENT_PERIOD));
+    }
+
+    #[test]
+    fn overdue_monthly_premium_includes_arrears() {
+        let mut policy = monthly_policy(100);
+        policy.total_paid = 100;
+        policy.next_payment_due = PAYMENT_PERIOD;
+        assert_eq!(premium_due(&policy, PAYMENT_PERIOD).unwrap(), (100, 2 * PAYMENT_PERIOD));
+        // Any part of a missed period counts as a whole one
+        assert_eq!(premium_due(&policy, 2 * PAYMENT_PERIOD + 1).unwrap(), (200, 3 * PAYMENT_PERIOD));
+    }
+
+    fn rate_table() -> RateTable {
+        RateTable {
+            theft_only_rate_bps: 200,
+            theft_and_loss_rate_bps: 350,
+            deductible_discount_bps: 5_000,
+            max_deductible_discount_bps: 1_000,
+            monthly_loading_bps: 500,
+            fraud_flag_surcharge_bps: 2_500,
+            updated_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn min_premium_scales_with_coverage_and_term() {
+        let table = rate_table();
+        let annual = |coverage_type, duration_days| {
+            calculate_min_premium(
+                &table,
+                100_000,
+                0,
+                coverage_type,
+                PaymentFrequency::Annual,
+                duration_days,
+                0,
+            )
+            .unwrap()
+        };
+        assert_eq!(annual(CoverageType::TheftOnly, 365), 2_000);
+        assert_eq!(annual(CoverageType::TheftAndLoss, 365), 3_500);
+        // 2_000 * 180 / 365 = 986.3, rounded down
+        assert_eq!(annual(CoverageType::TheftOnly, 180), 986);
+    }
+
+    #[test]
+    fn min_premium_applies_deductible_discount_and_fraud_surcharge() {
+        let table = rate_table();
+        let premium = |deductible_bps, fraud_flags| {
+            calculate_min_premium(
+                &table,
+                100_000,
+                deductible_bps,
+                CoverageType::TheftOnly,
+                PaymentFrequency::Annual,
+                365,
+                fraud_flags,
+            )
+            .unwrap()
+        };
+        assert_eq!(premium(1_000, 0), 1_900);
+        // The discount stops at the table's cap
+        assert_eq!(premium(5_000, 0), 1_800);
+        assert_eq!(premium(0, 1), 2_500);
+    }
+
+    #[test]
+    fn monthly_min_premium_covers_one_loaded_period() {
+        let premium = calculate_min_premium(
+            &rate_table(),
+            100_000,
+            0,
+            CoverageType::TheftOnly,
+            PaymentFrequency::Monthly,
+            365,
+            0,
+        );
+        // 2_000 * 30 / 365 * 1.05 = 172.6, whatever the term length
+        assert_eq!(premium.unwrap(), 172);
+    }
+
+    #[test]
+    fn cooling_off_refund_splits_pool_and_platform_fee() {
+        let policy = annual_policy(0, 1_000, 100);
+        let refund = calculate_cancellation_refund(&policy, DAY, 500).unwrap();
+        // The pool only holds the premium net of the fee; the treasury returns the rest
+        assert_eq!(refund, (900, 100, 0));
+    }
+
+    #[test]
+    fn unearned_premium_at_start_of_term() {
+        let policy = annual_policy(0, 1_000, 100);
+        assert_eq!(calculate_unearned_premium(&policy, 0).unwrap(), 900);
+        // Cancelling before the start date still only returns the pool share
+        assert_eq!(calculate_unearned_premium(&policy, -DAY).unwrap(), 900);
+    }
+
+    #[test]
+    fn unearned_premium_mid_term() {
+        let policy = annual_policy(0, 1_000, 100);
+        assert_eq!(calculate_unearned_premium(&policy, 73 * DAY).unwrap(), 720);
+        // Rounds down in the pool's favour
+        assert_eq!(calculate_unearned_premium(&policy, 100 * DAY).unwrap(), 653);
+    }
+
+    #[test]
+    fn unearned_premium_at_end_of_term() {
+        let policy = annual_policy(0, 1_000, 100);
+        assert_eq!(calculate_unearned_premium(&policy, 365 * DAY).unwrap(), 0);
+        assert_eq!(calculate_unearned_premium(&policy, 400 * DAY).unwrap(), 0);
+    }
+
+    #[test]
+    fn unearned_premium_only_counts_the_current_term() {
+        let mut policy = annual_policy(0, 1_000, 100);
+        start_next_term(&mut policy, 365 * DAY, 365).unwrap();
+        assert_eq!(policy.term_start, 365 * DAY);
+
+        // The renewal was repriced and the new premium paid
+        policy.total_paid += 2_000;
+        policy.term_paid = 2_000;
+        policy.term_fees_paid = 200;
+        policy.next_payment_due = policy.expiry_date;
+
+        assert_eq!(calculate_unearned_premium(&policy, 438 * DAY).unwrap(), 1_440);
+    }
+
+    #[test]
+    fn renewal_before_term_ends_earns_no_claim_free_year() {
+        let mut policy = annual_policy(0, 1_000, 100);
+        let expiry = policy.expiry_date;
+        assert_eq!(
+            start_next_term(&mut policy, expiry - 1, 365),
+            Err(ErrorCode::RenewalTooEarly.into())
+        );
+        assert_eq!(policy.claim_free_years, 0);
+        assert_eq!(policy.expiry_date, expiry);
+    }
+
+    #[test]
+    fn claim_free_terms_accumulate() {
+        let mut policy = annual_policy(0, 1_000, 100);
+        start_next_term(&mut policy, 365 * DAY, 365).unwrap();
+        assert_eq!(policy.claim_free_years, 1);
+        assert_eq!(policy.expiry_date, 730 * DAY);
+        start_next_term(&mut policy, 740 * DAY, 365).unwrap();
+        assert_eq!(policy.claim_free_years, 2);
+        assert_eq!(policy.expiry_date, 1_095 * DAY);
+    }
+
+    #[test]
+    fn payout_in_term_resets_claim_free_years() {
+        let mut policy = annual_policy(0, 1_000, 100);
+        policy.claim_free_years = 3;
+        policy.remaining_aggregate = 60_000;
+        start_next_term(&mut policy, 365 * DAY, 365).unwrap();
+        assert_eq!(policy.claim_free_years, 0);
+        assert_eq!(policy.remaining_aggregate, policy.aggregate_limit);
+    }
+
+    #[test]
+    fn cancellation_after_cooling_off_never_touches_the_treasury() {
+        let policy = annual_policy(0, 1_000, 100);
+        let (pool_refund, platform_fee_refund, cancellation_fee) =
+            calculate_cancellation_refund(&policy, 73 * DAY, 500).unwrap();
+        assert_eq!((pool_refund, platform_fee_refund, cancellation_fee), (684, 0, 36));
+        assert!(pool_refund + cancellation_fee <= policy.term_paid - policy.term_fees_paid);
+    }
+}
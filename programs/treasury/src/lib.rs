@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+pub use protocol_types::{AssetType, ParamChangeStatus};
+use protocol_types::{DEFAULT_TIMELOCK_DELAY, MAX_TIMELOCK_DELAY, MIN_TIMELOCK_DELAY};
 
 declare_id!("Trea5ury1111111111111111111111111111111111111");
 
@@ -13,6 +15,7 @@ pub mod treasury {
     ) -> Result<()> {
         let treasury_state = &mut ctx.accounts.treasury_state;
         treasury_state.authority = ctx.accounts.authority.key();
+        treasury_state.pending_authority = None;
         treasury_state.vault_usdc = ctx.accounts.vault_usdc.key();
         treasury_state.vault_sol = ctx.accounts.vault_sol.key();
         treasury_state.total_fees_collected = 0;
@@ -20,6 +23,13 @@ pub mod treasury {
         treasury_state.lp_service_fees = 0;
         treasury_state.total_withdrawn = 0;
         treasury_state.withdrawal_threshold = withdrawal_threshold;
+        treasury_state.policy_manager = Pubkey::default();
+        treasury_state.policy_manager_authority = Pubkey::default();
+        treasury_state.guardian = ctx.accounts.authority.key();
+        treasury_state.paused = false;
+        treasury_state.withdrawals_paused = false;
+        treasury_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+        treasury_state.param_change_count = 0;
         treasury_state.bump = ctx.bumps.treasury_state;
 
         emit!(TreasuryInitializedEvent {
@@ -37,6 +47,7 @@ pub mod treasury {
         amount: u64,
         asset_type: AssetType,
     ) -> Result<()> {
+        require!(!ctx.accounts.treasury_state.paused, ErrorCode::ProgramPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let treasury_state = &mut ctx.accounts.treasury_state;
@@ -95,6 +106,7 @@ pub mod treasury {
         amount: u64,
         asset_type: AssetType,
     ) -> Result<()> {
+        require!(!ctx.accounts.treasury_state.paused, ErrorCode::ProgramPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let treasury_state = &mut ctx.accounts.treasury_state;
@@ -148,10 +160,59 @@ pub mod treasury {
         Ok(())
     }
 
+    /// Returns a platform fee to a customer, e.g. when a policy is cancelled
+    /// within its cooling-off period. Only the policy manager can call this.
+    pub fn refund_platform_fee(
+        ctx: Context<RefundPlatformFee>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.treasury_state.paused, ErrorCode::ProgramPaused);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        require!(
+            ctx.accounts.vault_usdc.amount >= amount,
+            ErrorCode::InsufficientFunds
+        );
+
+        let seeds: [&[u8]; 2] = [b"treasury_state", &[treasury_state.bump]];
+        let signer = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_usdc.to_account_info(),
+                to: ctx.accounts.recipient_usdc.to_account_info(),
+                authority: treasury_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)?;
+
+        treasury_state.platform_fees = treasury_state.platform_fees
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        treasury_state.total_fees_collected = treasury_state.total_fees_collected
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+
+        emit!(PlatformFeeRefundedEvent {
+            amount,
+            recipient: ctx.accounts.recipient_usdc.key(),
+            platform_fees: treasury_state.platform_fees,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Platform fee refunded: {}", amount);
+        Ok(())
+    }
+
     pub fn withdraw_usdc(
         ctx: Context<WithdrawUSDC>,
         amount: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.treasury_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.treasury_state.withdrawals_paused, ErrorCode::WithdrawalsPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
         require!(
             ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
@@ -208,6 +269,8 @@ pub mod treasury {
         ctx: Context<WithdrawSOL>,
         amount: u64,
     ) -> Result<()> {
+        require!(!ctx.accounts.treasury_state.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.treasury_state.withdrawals_paused, ErrorCode::WithdrawalsPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
         require!(
             ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
@@ -261,7 +324,7 @@ pub mod treasury {
     }
 
     pub fn update_withdrawal_threshold(
-        ctx: Context<UpdateWithdrawalThreshold>,
+        ctx: Context<QueueParamChange>,
         new_threshold: u64,
     ) -> Result<()> {
         require!(
@@ -269,32 +332,74 @@ pub mod treasury {
             ErrorCode::Unauthorized
         );
 
+        let change = ParamChange::WithdrawalThreshold { new_threshold };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.treasury_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    /// The trusted policy manager can refund fees out of the vault, so
+    /// changing it is timelocked.
+    pub fn set_trusted_callers(
+        ctx: Context<QueueParamChange>,
+        policy_manager: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TrustedCallers { policy_manager };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.treasury_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // The new key only takes over once it signs accept_authority, so a
+        // mistyped address can be cancelled instead of locking out the admin.
         let treasury_state = &mut ctx.accounts.treasury_state;
-        let old_threshold = treasury_state.withdrawal_threshold;
-        treasury_state.withdrawal_threshold = new_threshold;
+        treasury_state.pending_authority = Some(new_authority);
 
-        emit!(WithdrawalThresholdUpdatedEvent {
-            old_threshold,
-            new_threshold,
+        emit!(AuthorityTransferProposedEvent {
+            current_authority: treasury_state.authority,
+            pending_authority: new_authority,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Withdrawal threshold updated: {} -> {}", old_threshold, new_threshold);
+        msg!("Authority transfer proposed: {} -> {}", treasury_state.authority, new_authority);
         Ok(())
     }
 
-    pub fn update_authority(
-        ctx: Context<UpdateAuthority>,
-        new_authority: Pubkey,
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
     ) -> Result<()> {
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        let new_authority = treasury_state.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ctx.accounts.new_authority.key() == new_authority,
             ErrorCode::Unauthorized
         );
 
-        let treasury_state = &mut ctx.accounts.treasury_state;
         let old_authority = treasury_state.authority;
         treasury_state.authority = new_authority;
+        treasury_state.pending_authority = None;
 
         emit!(AuthorityUpdatedEvent {
             old_authority,
@@ -306,6 +411,27 @@ pub mod treasury {
         Ok(())
     }
 
+    pub fn cancel_authority_transfer(
+        ctx: Context<CancelAuthorityTransfer>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        let cancelled_authority = treasury_state.pending_authority.take().ok_or(ErrorCode::NoPendingAuthority)?;
+
+        emit!(AuthorityTransferCancelledEvent {
+            authority: treasury_state.authority,
+            cancelled_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Authority transfer to {} cancelled", cancelled_authority);
+        Ok(())
+    }
+
     pub fn get_treasury_stats(
         ctx: Context<GetTreasuryStats>,
     ) -> Result<TreasuryStats> {
@@ -324,6 +450,222 @@ pub mod treasury {
                 .saturating_sub(treasury_state.total_withdrawn),
         })
     }
+
+    pub fn update_timelock_delay(
+        ctx: Context<QueueParamChange>,
+        new_delay: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let change = ParamChange::TimelockDelay { new_delay };
+        validate_param_change(&change)?;
+        queue_param_change(
+            &mut ctx.accounts.treasury_state,
+            &mut ctx.accounts.pending_change,
+            change,
+            ctx.bumps.pending_change,
+        )
+    }
+
+    /// Applies a queued change once its ETA has passed. Permissionless, since
+    /// the change itself was already authorized when it was queued.
+    pub fn execute_parameter_change(
+        ctx: Context<ExecuteParameterChange>,
+    ) -> Result<()> {
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= pending_change.eta, ErrorCode::TimelockNotElapsed);
+
+        validate_param_change(&pending_change.change)?;
+        apply_param_change(&mut ctx.accounts.treasury_state, pending_change.change)?;
+        pending_change.status = ParamChangeStatus::Executed;
+
+        emit!(ParamChangeExecutedEvent {
+            id: pending_change.id,
+            change: pending_change.change,
+            timestamp: now,
+        });
+
+        msg!("Parameter change {} executed", pending_change.id);
+        Ok(())
+    }
+
+    pub fn cancel_parameter_change(
+        ctx: Context<CancelParameterChange>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let pending_change = &mut ctx.accounts.pending_change;
+
+        require!(
+            pending_change.status == ParamChangeStatus::Queued,
+            ErrorCode::ChangeNotQueued
+        );
+
+        pending_change.status = ParamChangeStatus::Cancelled;
+
+        emit!(ParamChangeCancelledEvent {
+            id: pending_change.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Parameter change {} cancelled", pending_change.id);
+        Ok(())
+    }
+
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury_state.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        let old_guardian = treasury_state.guardian;
+        treasury_state.guardian = new_guardian;
+
+        emit!(GuardianUpdatedEvent {
+            old_guardian,
+            new_guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Guardian updated: {} -> {}", old_guardian, new_guardian);
+        Ok(())
+    }
+
+    /// Circuit breaker. Only the guardian can flip these, so a pause doesn't
+    /// have to wait on the authority key or the timelock.
+    pub fn set_pause_state(
+        ctx: Context<SetPauseState>,
+        paused: bool,
+        withdrawals_paused: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.guardian.key() == ctx.accounts.treasury_state.guardian,
+            ErrorCode::Unauthorized
+        );
+
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        treasury_state.paused = paused;
+        treasury_state.withdrawals_paused = withdrawals_paused;
+
+        emit!(PauseStateUpdatedEvent {
+            guardian: ctx.accounts.guardian.key(),
+            paused,
+            withdrawals_paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Pause state updated: paused={}, withdrawals_paused={}", paused, withdrawals_paused);
+        Ok(())
+    }
+}
+
+/// Stores `change` as the next pending change, executable after the current timelock delay
+fn queue_param_change(
+    treasury_state: &mut TreasuryState,
+    pending_change: &mut PendingChange,
+    change: ParamChange,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    treasury_state.param_change_count = treasury_state.param_change_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    pending_change.id = treasury_state.param_change_count;
+    pending_change.change = change;
+    pending_change.queued_at = now;
+    pending_change.eta = now
+        .checked_add(treasury_state.timelock_delay)
+        .ok_or(ErrorCode::Overflow)?;
+    pending_change.status = ParamChangeStatus::Queued;
+    pending_change.bump = bump;
+
+    emit!(ParamChangeQueuedEvent {
+        id: pending_change.id,
+        change,
+        eta: pending_change.eta,
+        timestamp: now,
+    });
+
+    msg!("Parameter change {} queued: {:?}, executable at {}", pending_change.id, change, pending_change.eta);
+    Ok(())
+}
+
+/// Bounds checks shared by queueing and execution
+fn validate_param_change(change: &ParamChange) -> Result<()> {
+    if let ParamChange::TimelockDelay { new_delay } = change {
+        require!(
+            (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(new_delay),
+            ErrorCode::InvalidTimelockDelay
+        );
+    }
+    Ok(())
+}
+
+fn apply_param_change(treasury_state: &mut TreasuryState, change: ParamChange) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    match change {
+        ParamChange::WithdrawalThreshold { new_threshold } => {
+            let old_threshold = treasury_state.withdrawal_threshold;
+            treasury_state.withdrawal_threshold = new_threshold;
+
+            emit!(WithdrawalThresholdUpdatedEvent {
+                old_threshold,
+                new_threshold,
+                timestamp,
+            });
+
+            msg!("Withdrawal threshold updated: {} -> {}", old_threshold, new_threshold);
+        }
+        ParamChange::TrustedCallers { policy_manager } => {
+            // The policy manager signs its CPIs with a PDA derived from its
+            // program ID; that PDA is the only accepted caller.
+            treasury_state.policy_manager = policy_manager;
+            treasury_state.policy_manager_authority =
+                Pubkey::find_program_address(&[b"policy_authority"], &policy_manager).0;
+
+            emit!(TrustedCallersUpdatedEvent {
+                policy_manager,
+                policy_manager_authority: treasury_state.policy_manager_authority,
+                timestamp,
+            });
+
+            msg!("Trusted callers updated: policy_manager={}", policy_manager);
+        }
+        ParamChange::TimelockDelay { new_delay } => {
+            let old_delay = treasury_state.timelock_delay;
+            treasury_state.timelock_delay = new_delay;
+
+            emit!(TimelockDelayUpdatedEvent {
+                old_delay,
+                new_delay,
+                timestamp,
+            });
+
+            msg!("Timelock delay updated: {}s -> {}s", old_delay, new_delay);
+        }
+    }
+
+    Ok(())
 }
 
 // ========== CONTEXTS ==========
@@ -435,6 +777,34 @@ pub struct CollectLPServiceFee<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct RefundPlatformFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_usdc"],
+        bump
+    )]
+    pub vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_usdc: Account<'info, TokenAccount>,
+
+    /// Only the policy-manager program's authority PDA can call this
+    #[account(
+        constraint = policy_manager.key() == treasury_state.policy_manager_authority @ ErrorCode::UnauthorizedCaller
+    )]
+    pub policy_manager: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawUSDC<'info> {
     #[account(
@@ -486,7 +856,7 @@ pub struct WithdrawSOL<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateWithdrawalThreshold<'info> {
+pub struct ProposeAuthority<'info> {
     #[account(
         mut,
         seeds = [b"treasury_state"],
@@ -498,7 +868,19 @@ pub struct UpdateWithdrawalThreshold<'info> {
 }
 
 #[derive(Accounts)]
-pub struct UpdateAuthority<'info> {
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
     #[account(
         mut,
         seeds = [b"treasury_state"],
@@ -531,12 +913,98 @@ pub struct GetTreasuryStats<'info> {
     pub vault_sol: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct QueueParamChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingChange::INIT_SPACE,
+        seeds = [b"param_change".as_ref(), &(treasury_state.param_change_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelParameterChange<'info> {
+    #[account(
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"param_change".as_ref(), &pending_change.id.to_le_bytes()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseState<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_state"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    pub guardian: Signer<'info>,
+}
+
 // ========== ACCOUNT STRUCTS ==========
 
 #[account]
 #[derive(InitSpace)]
 pub struct TreasuryState {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
     pub vault_usdc: Pubkey,
     pub vault_sol: Pubkey,
     pub total_fees_collected: u64,
@@ -544,15 +1012,34 @@ pub struct TreasuryState {
     pub lp_service_fees: u64,
     pub total_withdrawn: u64,
     pub withdrawal_threshold: u64,
+    pub policy_manager: Pubkey,
+    pub policy_manager_authority: Pubkey,
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub withdrawals_paused: bool,
+    pub timelock_delay: i64,
+    pub param_change_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingChange {
+    pub id: u64,
+    pub change: ParamChange,
+    pub queued_at: i64,
+    pub eta: i64,
+    pub status: ParamChangeStatus,
     pub bump: u8,
 }
 
 // ========== ENUMS ==========
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
-pub enum AssetType {
-    USDC,
-    SOL,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ParamChange {
+    WithdrawalThreshold { new_threshold: u64 },
+    TrustedCallers { policy_manager: Pubkey },
+    TimelockDelay { new_delay: i64 },
 }
 
 // ========== RETURN TYPES ==========
@@ -585,6 +1072,14 @@ pub struct PlatformFeeCollectedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PlatformFeeRefundedEvent {
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub platform_fees: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct LPServiceFeeCollectedEvent {
     pub amount: u64,
@@ -602,6 +1097,13 @@ pub struct WithdrawalEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TrustedCallersUpdatedEvent {
+    pub policy_manager: Pubkey,
+    pub policy_manager_authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct WithdrawalThresholdUpdatedEvent {
     pub old_threshold: u64,
@@ -609,6 +1111,20 @@ pub struct WithdrawalThresholdUpdatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AuthorityTransferProposedEvent {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelledEvent {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AuthorityUpdatedEvent {
     pub old_authority: Pubkey,
@@ -616,6 +1132,49 @@ pub struct AuthorityUpdatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ParamChangeQueuedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeExecutedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeCancelledEvent {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimelockDelayUpdatedEvent {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseStateUpdatedEvent {
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub withdrawals_paused: bool,
+    pub timestamp: i64,
+}
+
 // ========== ERRORS ==========
 
 #[error_code]
@@ -637,4 +1196,25 @@ pub enum ErrorCode {
 
     #[msg("Unauthorized")]
     Unauthorized,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Timelock delay out of bounds")]
+    InvalidTimelockDelay,
+
+    #[msg("Parameter change is not queued")]
+    ChangeNotQueued,
+
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Withdrawals are paused")]
+    WithdrawalsPaused,
+
+    #[msg("Caller is not a trusted program authority")]
+    UnauthorizedCaller,
 }
\ No newline at end of file